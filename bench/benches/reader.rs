@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use dangerous::{input, ByteArray, BytesReader, Input, Invalid};
+use dangerous::{input, ByteArray, BytesReader, Input, Invalid, StringReader};
 
 fn bench_consume(c: &mut Criterion) {
     c.bench_function("consume_u8_ok", |b| {
@@ -103,5 +103,75 @@ fn bench_peek_eq(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_peek_eq, bench_consume, bench_read_num);
+fn bench_take_while(c: &mut Criterion) {
+    let ones = [b'1'; 4096];
+    let pairs = b"12".repeat(2048);
+    let text = "hello world ".repeat(512);
+
+    c.bench_function("take_while_u8", |b| {
+        b.iter(|| {
+            input(black_box(&ones[..]))
+                .read_all(|r: &mut BytesReader<'_, Invalid>| Ok(r.take_while(b'1')))
+                .unwrap();
+        })
+    });
+
+    c.bench_function("take_while_bytes", |b| {
+        b.iter(|| {
+            input(black_box(&pairs[..]))
+                .read_all(|r: &mut BytesReader<'_, Invalid>| Ok(r.take_while(b"12")))
+                .unwrap();
+        })
+    });
+
+    c.bench_function("take_while_char_fn", |b| {
+        b.iter(|| {
+            input(black_box(text.as_str()))
+                .read_all(|r: &mut StringReader<'_, Invalid>| {
+                    Ok(r.take_while(|c: char| c.is_ascii_alphabetic() || c == ' '))
+                })
+                .unwrap();
+        })
+    });
+
+    c.bench_function("take_str_while", |b| {
+        b.iter(|| {
+            input(black_box(text.as_bytes()))
+                .read_all(|r: &mut BytesReader<'_, Invalid>| {
+                    r.take_str_while(|c| c.is_ascii_alphabetic() || c == ' ')
+                })
+                .unwrap();
+        })
+    });
+}
+
+fn bench_into_string(c: &mut Criterion) {
+    let ascii = "hello world ".repeat(512);
+    let utf8 = "héllo wörld ".repeat(512);
+
+    c.bench_function("into_string_ascii", |b| {
+        b.iter(|| {
+            input(black_box(ascii.as_bytes()))
+                .into_string::<Invalid>()
+                .unwrap();
+        })
+    });
+
+    c.bench_function("into_string_utf8", |b| {
+        b.iter(|| {
+            input(black_box(utf8.as_bytes()))
+                .into_string::<Invalid>()
+                .unwrap();
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_peek_eq,
+    bench_consume,
+    bench_read_num,
+    bench_take_while,
+    bench_into_string
+);
 criterion_main!(benches);
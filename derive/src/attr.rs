@@ -0,0 +1,120 @@
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Expr, LitStr, Path, Result};
+
+/// Byte order of a multi-byte number.
+#[derive(Copy, Clone)]
+pub(crate) enum Endian {
+    Little,
+    Big,
+    Native,
+}
+
+impl Endian {
+    pub(crate) fn suffix(self) -> &'static str {
+        match self {
+            Self::Little => "le",
+            Self::Big => "be",
+            Self::Native => "ne",
+        }
+    }
+}
+
+/// Attributes placed on the struct.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    pub(crate) endian: Option<Endian>,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("dangerous"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if let Some(endian) = parse_endian(&meta) {
+                    set(&meta, &mut this.endian, endian)
+                } else {
+                    Err(meta.error("unsupported struct attribute"))
+                }
+            })?;
+        }
+        Ok(this)
+    }
+}
+
+/// Attributes placed on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    pub(crate) endian: Option<Endian>,
+    pub(crate) consume: Option<Expr>,
+    pub(crate) len: Option<Expr>,
+    pub(crate) prefix: Option<Expr>,
+    pub(crate) policy: Option<Expr>,
+    pub(crate) verify: Option<Path>,
+    pub(crate) expected: Option<LitStr>,
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("dangerous"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if let Some(endian) = parse_endian(&meta) {
+                    set(&meta, &mut this.endian, endian)
+                } else if meta.path.is_ident("consume") {
+                    set(&meta, &mut this.consume, meta.value()?.parse()?)
+                } else if meta.path.is_ident("len") {
+                    set(&meta, &mut this.len, meta.value()?.parse()?)
+                } else if meta.path.is_ident("prefix") {
+                    set(&meta, &mut this.prefix, meta.value()?.parse()?)
+                } else if meta.path.is_ident("policy") {
+                    set(&meta, &mut this.policy, meta.value()?.parse()?)
+                } else if meta.path.is_ident("verify") {
+                    set(&meta, &mut this.verify, meta.value()?.parse()?)
+                } else if meta.path.is_ident("expected") {
+                    set(&meta, &mut this.expected, meta.value()?.parse()?)
+                } else {
+                    Err(meta.error("unsupported field attribute"))
+                }
+            })?;
+        }
+        if this.len.is_some() && this.prefix.is_some() {
+            return Err(syn::Error::new_spanned(
+                &attrs[0],
+                "`len` and `prefix` cannot be used together",
+            ));
+        }
+        if this.expected.is_some() && this.verify.is_none() {
+            return Err(syn::Error::new_spanned(
+                &attrs[0],
+                "`expected` requires `verify`",
+            ));
+        }
+        Ok(this)
+    }
+}
+
+fn parse_endian(meta: &ParseNestedMeta<'_>) -> Option<Endian> {
+    if meta.path.is_ident("le") {
+        Some(Endian::Little)
+    } else if meta.path.is_ident("be") {
+        Some(Endian::Big)
+    } else if meta.path.is_ident("ne") {
+        Some(Endian::Native)
+    } else {
+        None
+    }
+}
+
+fn set<T>(meta: &ParseNestedMeta<'_>, slot: &mut Option<T>, value: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(meta.error("duplicate attribute"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
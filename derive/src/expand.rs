@@ -0,0 +1,277 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Data, DeriveInput, Error, Field, Fields, GenericParam, Ident, Lifetime, LitStr, Path, Result,
+    Type,
+};
+
+use crate::attr::{ContainerAttrs, Endian, FieldAttrs};
+
+pub(crate) fn derive_read(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`Read` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Read` can only be derived for structs",
+            ))
+        }
+    };
+    let container = ContainerAttrs::parse(&input.attrs)?;
+
+    // Input borrowed by the struct is tied to its first lifetime, otherwise
+    // the method is generic over the input lifetime.
+    let lifetime = input.generics.params.iter().find_map(|param| match param {
+        GenericParam::Lifetime(def) => Some(def.lifetime.clone()),
+        _ => None,
+    });
+    let (input_lt, method_lt) = if let Some(lt) = lifetime {
+        (lt, None)
+    } else {
+        let lt = Lifetime::new("'i", Span::call_site());
+        (lt.clone(), Some(quote!(#lt,)))
+    };
+    // The reader and error type are hygienic so they can't clash with fields
+    // referenced in attribute expressions.
+    let r = Ident::new("r", Span::mixed_site());
+    let e = Ident::new("E", Span::mixed_site());
+
+    let mut reads = Vec::with_capacity(fields.len());
+    let mut names = Vec::with_capacity(fields.len());
+    for field in fields {
+        let name = field.ident.as_ref().expect("named field");
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let context = LitStr::new(&name.to_string(), name.span());
+        let ty = &field.ty;
+        let value = read_value(field, &attrs, container.endian, &r, &e)?;
+        let consume = attrs
+            .consume
+            .as_ref()
+            .map(|prefix| quote_spanned!(prefix.span()=> #r.consume(#prefix)?;));
+        let read = if let Some(verify) = &attrs.verify {
+            let expected = attrs
+                .expected
+                .clone()
+                .unwrap_or_else(|| LitStr::new(&format!("valid {}", name), name.span()));
+            quote! {
+                #r.try_expect(#expected, |#r| {
+                    let value: #ty = #value;
+                    if #verify(&value) {
+                        ::core::result::Result::Ok(::core::option::Option::Some(value))
+                    } else {
+                        ::core::result::Result::Ok(::core::option::Option::None)
+                    }
+                })
+            }
+        } else {
+            quote! {{
+                let value: #ty = #value;
+                ::core::result::Result::Ok(value)
+            }}
+        };
+        reads.push(quote! {
+            let #name: #ty = #r.context(#context, |#r| {
+                #consume
+                #read
+            })?;
+        });
+        names.push(name);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let doc = format!(
+        "Read a [`{}`] from a [`BytesReader`](::dangerous::BytesReader).",
+        ident
+    );
+    let errors = LitStr::new(
+        &format!(
+            "Returns an error if any field of the `{}` failed to be read.",
+            ident
+        ),
+        Span::call_site(),
+    );
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #doc]
+            ///
+            /// # Errors
+            ///
+            #[doc = #errors]
+            pub fn read<#method_lt #e>(
+                #r: &mut ::dangerous::BytesReader<#input_lt, #e>,
+            ) -> ::core::result::Result<Self, #e>
+            where
+                #e: ::dangerous::Error<#input_lt>,
+            {
+                #(#reads)*
+                ::core::result::Result::Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
+
+/// Returns an expression reading the value of a field, propagating errors
+/// with `?`.
+fn read_value(
+    field: &Field,
+    attrs: &FieldAttrs,
+    endian: Option<Endian>,
+    r: &Ident,
+    e: &Ident,
+) -> Result<TokenStream> {
+    let ty = &field.ty;
+    let span = ty.span();
+    let kind = Kind::of(ty);
+
+    let taken = match (&attrs.len, &attrs.prefix) {
+        (Some(len), None) => Some(quote! {{
+            // A negative or overflowing length could never be taken.
+            let len = #r.expect("valid length", |_| {
+                <usize as ::core::convert::TryFrom<_>>::try_from(#len).ok()
+            })?;
+            #r.take(len)?
+        }}),
+        (None, Some(prefix)) => {
+            Some(quote_spanned!(prefix.span()=> #r.take_length_prefixed(#prefix)?))
+        }
+        _ => None,
+    };
+    if let Some(taken) = taken {
+        return match kind {
+            Kind::Bytes => Ok(taken),
+            Kind::Slice => Ok(quote_spanned!(span=> #taken.as_dangerous())),
+            Kind::Str => Ok(quote_spanned!(span=> #taken.to_dangerous_str::<#e>()?)),
+            Kind::String => Ok(quote_spanned!(span=> ::dangerous::Input::into_string::<#e>(#taken)?)),
+            _ => Err(Error::new(
+                span,
+                "`len` and `prefix` are only supported on `&[u8]`, `&str`, `Bytes` and `String` fields",
+            )),
+        };
+    }
+    if attrs.policy.is_some() && !matches!(kind, Kind::Float(_)) {
+        return Err(Error::new(
+            span,
+            "`policy` is only supported on float fields",
+        ));
+    }
+    match kind {
+        Kind::Byte(num) => {
+            let read = format_ident!("read_{}", num);
+            Ok(quote_spanned!(span=> #r.#read()?))
+        }
+        Kind::Int(num) | Kind::Float(num) => {
+            let endian = attrs.endian.or(endian).ok_or_else(|| {
+                Error::new(
+                    span,
+                    "missing byte order, add `#[dangerous(le)]`, `#[dangerous(be)]` or `#[dangerous(ne)]`",
+                )
+            })?;
+            let read = format_ident!("read_{}_{}", num, endian.suffix());
+            if let Kind::Float(_) = kind {
+                let policy = attrs.policy.as_ref().map_or_else(
+                    || quote!(::dangerous::input::FloatPolicy::any()),
+                    |p| quote!(#p),
+                );
+                Ok(quote_spanned!(span=> #r.#read(#policy)?))
+            } else {
+                Ok(quote_spanned!(span=> #r.#read()?))
+            }
+        }
+        Kind::Array => Ok(quote_spanned!(span=> #r.take_array()?.into_dangerous())),
+        Kind::Bytes | Kind::Slice | Kind::Str | Kind::String => Err(Error::new(
+            span,
+            "missing length, add `#[dangerous(len = ...)]` or `#[dangerous(prefix = ...)]`",
+        )),
+        Kind::Nested => Ok(quote_spanned!(span=> <#ty>::read(#r)?)),
+    }
+}
+
+/// How a field is read, decided by its type.
+#[derive(Copy, Clone)]
+enum Kind {
+    /// `u8` or `i8`.
+    Byte(&'static str),
+    /// A multi-byte integer.
+    Int(&'static str),
+    /// `f32` or `f64`.
+    Float(&'static str),
+    /// `[u8; N]`.
+    Array,
+    /// `dangerous::Bytes<'i>`.
+    Bytes,
+    /// `&'i [u8]`.
+    Slice,
+    /// `&'i str`.
+    Str,
+    /// `dangerous::String<'i>`.
+    String,
+    /// Any other type, read with its own `read` function.
+    Nested,
+}
+
+impl Kind {
+    fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Path(path) if path.qself.is_none() => {
+                let last = match path.path.segments.last() {
+                    Some(last) => last.ident.to_string(),
+                    None => return Self::Nested,
+                };
+                match last.as_str() {
+                    "u8" => Self::Byte("u8"),
+                    "i8" => Self::Byte("i8"),
+                    "u16" => Self::Int("u16"),
+                    "i16" => Self::Int("i16"),
+                    "u32" => Self::Int("u32"),
+                    "i32" => Self::Int("i32"),
+                    "u64" => Self::Int("u64"),
+                    "i64" => Self::Int("i64"),
+                    "u128" => Self::Int("u128"),
+                    "i128" => Self::Int("i128"),
+                    "f32" => Self::Float("f32"),
+                    "f64" => Self::Float("f64"),
+                    "Bytes" if is_dangerous_path(&path.path) => Self::Bytes,
+                    "String" if is_dangerous_path(&path.path) => Self::String,
+                    _ => Self::Nested,
+                }
+            }
+            Type::Reference(reference) => match &*reference.elem {
+                Type::Slice(slice) if is_ident(&slice.elem, "u8") => Self::Slice,
+                elem if is_ident(elem, "str") => Self::Str,
+                _ => Self::Nested,
+            },
+            Type::Array(array) if is_ident(&array.elem, "u8") => Self::Array,
+            Type::Group(group) => Self::of(&group.elem),
+            _ => Self::Nested,
+        }
+    }
+}
+
+/// Returns `true` if the path is unqualified or qualified with
+/// `dangerous` or `dangerous::input`.
+fn is_dangerous_path(path: &Path) -> bool {
+    let mut modules = path.segments.iter().rev().skip(1).map(|s| &s.ident);
+    match (modules.next(), modules.next(), modules.next()) {
+        (None, _, _) => path.leading_colon.is_none(),
+        (Some(m), None, _) => m == "dangerous",
+        (Some(input), Some(m), None) => input == "input" && m == "dangerous",
+        _ => false,
+    }
+}
+
+fn is_ident(ty: &Type, ident: &str) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident(ident),
+        _ => false,
+    }
+}
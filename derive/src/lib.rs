@@ -0,0 +1,111 @@
+//! Derive macros for [`dangerous`](https://docs.rs/dangerous).
+//!
+//! This crate is re-exported by `dangerous` with the `derive` feature enabled
+//! and should not be depended on directly.
+
+#![forbid(unsafe_code)]
+#![deny(
+    unused,
+    missing_docs,
+    rust_2018_idioms,
+    future_incompatible,
+    clippy::all,
+    clippy::correctness,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic
+)]
+
+mod attr;
+mod expand;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives a `read` function for a struct.
+///
+/// The generated function has the signature:
+///
+/// ```ignore
+/// pub fn read<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+/// where
+///     E: Error<'i>;
+/// ```
+///
+/// If the struct has a lifetime, the first is used as the input lifetime so
+/// fields can borrow from the input.
+///
+/// Fields are read in order, each within a context named after the field.
+/// How a field is read is decided by its type:
+///
+/// | Type                          | Read with                                   |
+/// | ----------------------------- | ------------------------------------------- |
+/// | `u8`, `i8`                    | `read_u8()`, `read_i8()`                    |
+/// | `u16` ..= `i128`              | `read_<ty>_<endian>()`                      |
+/// | `f32`, `f64`                  | `read_<ty>_<endian>(policy)`                |
+/// | `[u8; N]`                     | `take_array()`                              |
+/// | `&[u8]`, `&str`, `Bytes`, `String` | `take(len)` or `take_length_prefixed(prefix)` |
+/// | anything else                 | `<T>::read(r)`                              |
+///
+/// `Bytes` and `String` are only recognised unqualified or qualified with
+/// `dangerous` or `dangerous::input`, so a `Bytes` or `String` of another
+/// crate is read with its own `read` function.
+///
+/// # Attributes
+///
+/// On the struct:
+///
+/// - `#[dangerous(le)]`, `#[dangerous(be)]` or `#[dangerous(ne)]`: the default
+///   byte order for numbers.
+///
+/// On a field:
+///
+/// - `#[dangerous(le)]`, `#[dangerous(be)]` or `#[dangerous(ne)]`: the byte
+///   order of the number, overriding the struct default.
+/// - `#[dangerous(consume = <expr>)]`: consume a value, such as a magic
+///   constant, before reading the field.
+/// - `#[dangerous(len = <expr>)]`: the length of input to take. Previously
+///   read fields are in scope, so this can name a length field.
+/// - `#[dangerous(prefix = <expr>)]`: the [`LengthPrefix`] read before the
+///   input to take.
+/// - `#[dangerous(policy = <expr>)]`: the [`FloatPolicy`] for a float,
+///   defaulting to `FloatPolicy::any()`.
+/// - `#[dangerous(verify = <path>)]`: a `fn(&T) -> bool` the value must pass,
+///   with `#[dangerous(expected = "...")]` describing what was expected
+///   (defaulting to `valid <field>`).
+///
+/// # Example
+///
+/// ```ignore
+/// use dangerous::{Expected, Input};
+///
+/// #[derive(dangerous::Read)]
+/// #[dangerous(be)]
+/// struct Packet<'a> {
+///     #[dangerous(consume = b"PK", verify = is_supported, expected = "supported version")]
+///     version: u8,
+///     len: u16,
+///     #[dangerous(len = len)]
+///     body: &'a [u8],
+/// }
+///
+/// fn is_supported(version: &u8) -> bool {
+///     *version == 1
+/// }
+///
+/// let input = dangerous::input(b"PK\x01\x00\x02hi");
+/// let packet = input.read_all::<_, _, Expected<'_>>(Packet::read).unwrap();
+///
+/// assert_eq!(packet.body, b"hi");
+/// ```
+///
+/// [`LengthPrefix`]: https://docs.rs/dangerous/latest/dangerous/input/enum.LengthPrefix.html
+/// [`FloatPolicy`]: https://docs.rs/dangerous/latest/dangerous/input/struct.FloatPolicy.html
+#[proc_macro_derive(Read, attributes(dangerous))]
+pub fn derive_read(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::derive_read(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
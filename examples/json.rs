@@ -64,17 +64,13 @@ where
         r.consume(b'[')?;
         skip_whitespace(r);
         if !r.peek_eq(b']') {
-            loop {
-                let val = read_value(r)?;
-                skip_whitespace(r);
-                items.push(val);
-                if !r.at_end() && r.peek_eq(b',') {
-                    r.skip(1)?;
-                    continue;
-                } else {
-                    break;
-                }
-            }
+            items = r
+                .separated(b',', |r| {
+                    let val = read_value(r)?;
+                    skip_whitespace(r);
+                    Ok(val)
+                })
+                .collect::<Result<_, _>>()?;
         }
         skip_whitespace(r);
         r.consume(b']')?;
@@ -92,21 +88,17 @@ where
         r.consume(b'{')?;
         skip_whitespace(r);
         if !r.peek_eq(b'}') {
-            loop {
-                let key = r.context("json object key", read_str)?;
-                skip_whitespace(r);
-                r.consume(b':')?;
-                skip_whitespace(r);
-                let val = read_value(r)?;
-                skip_whitespace(r);
-                items.push((key, val));
-                if !r.at_end() && r.peek_eq(b',') {
-                    r.skip(1)?;
-                    continue;
-                } else {
-                    break;
-                }
-            }
+            items = r
+                .separated(b',', |r| {
+                    let key = r.context("json object key", read_str)?;
+                    skip_whitespace(r);
+                    r.consume(b':')?;
+                    skip_whitespace(r);
+                    let val = read_value(r)?;
+                    skip_whitespace(r);
+                    Ok((key, val))
+                })
+                .collect::<Result<_, _>>()?;
         }
         r.consume(b'}')?;
         Ok(items)
@@ -4,10 +4,11 @@
 //! followed by a single byte that denotes the the UTF-8 body length we need to
 //! read. Our protocol expects a version of `1`.
 
-use std::error::Error as StdError;
 use std::io;
 
-use dangerous::{BytesReader, Error, Expected, Input, Invalid, ToRetryRequirement};
+use dangerous::input::LengthPrefix;
+use dangerous::stream::{StreamDecoder, StreamError};
+use dangerous::{BytesReader, Error, Expected, Input, Invalid};
 
 const VALID_MESSAGE: &[u8] = &[
     0x01, // version: 1
@@ -27,75 +28,28 @@ struct Message<'a> {
 }
 
 fn main() {
-    let mut decoder = Decoder::new();
-
     // Read a valid message
-    let message = decoder
-        .read_and_decode_message(&mut Stream::new(VALID_MESSAGE))
+    let mut decoder = StreamDecoder::new(Stream::new(VALID_MESSAGE));
+    let body = decoder
+        .decode(|r| decode_message::<Invalid>(r).map(|message| message.body.to_owned()))
         .unwrap();
 
-    println!("{}", message.body);
+    println!("{}", body.unwrap());
 
     // Read a invalid message
-    let err = decoder
-        .read_and_decode_message(&mut Stream::new(INVALID_MESSAGE))
-        .unwrap_err();
-
-    eprintln!("error reading message: {}", err);
-}
-
-pub struct Decoder {
-    buf: [u8; 256],
-}
-
-impl Decoder {
-    fn new() -> Self {
-        Self { buf: [0u8; 256] }
-    }
-
-    fn read_and_decode_message<'i, R>(
-        &'i mut self,
-        mut read: R,
-    ) -> Result<Message<'i>, Box<dyn StdError + 'i>>
-    where
-        R: io::Read,
-    {
-        let mut written_cur = 0;
-        let mut expects_cur = 0;
-        loop {
-            // Read bytes into buffer
-            written_cur += read.read(&mut self.buf[written_cur..])?;
-            // Only decode the buffer if we have enough bytes to try again
-            if expects_cur > written_cur {
-                println!(
-                    "not enough to decode, waiting for {} bytes",
-                    expects_cur - written_cur
-                );
-                continue;
-            }
-            // Try and decode the input, working out if we need more, or the
-            // input is invalid.
-            //
-            // FIXME: This would realistically return the decoded message or any
-            // error, but we can't mut borrow and return immutable within a loop
-            // yet. See: https://github.com/rust-lang/rust/issues/51132
-            let input = dangerous::input(&self.buf[..written_cur]);
-            match input.read_all(decode_message) {
-                Err(err) => match Invalid::to_retry_requirement(&err) {
-                    Some(req) => {
-                        expects_cur += req.continue_after();
-                        continue;
-                    }
-                    None => break,
-                },
-                Ok(_) => break,
-            }
+    let mut decoder = StreamDecoder::new(Stream::new(INVALID_MESSAGE));
+    match decoder.decode(|r| decode_message::<Invalid>(r).map(|message| message.body.to_owned())) {
+        // The stream decoder only returns errors that don't borrow the input,
+        // so we decode what was buffered again for a detailed error.
+        Err(StreamError::Decode(_)) => {
+            let err = dangerous::input(decoder.buffer())
+                .into_bound()
+                .read_all::<_, _, Expected<'_>>(decode_message)
+                .unwrap_err();
+            eprintln!("error reading message: {}", err);
         }
-        // Decode the input returning the message or any error, see above why
-        // this is required.
-        dangerous::input(&self.buf[..written_cur])
-            .read_all(decode_message)
-            .map_err(Box::<Expected>::into)
+        Err(err) => eprintln!("error reading stream: {}", err),
+        Ok(body) => println!("{:?}", body),
     }
 }
 
@@ -106,11 +60,9 @@ where
     r.context("message", |r| {
         // Expect version 1
         r.context("version", |r| r.consume(0x01))?;
-        // Read the body length
-        let body_len = r.context("body len", BytesReader::read)?;
-        // Take the body input
+        // Take the body input following its length
         let body = r.context("body", |r| {
-            let body_input = r.take(body_len as usize)?;
+            let body_input = r.take_length_prefixed(LengthPrefix::U8)?;
             // Decode the body input as a UTF-8 str
             body_input.to_dangerous_str()
         })?;
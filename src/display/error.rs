@@ -1,10 +1,16 @@
+use core::ops::Range;
+
 use crate::error::{self, Context};
 use crate::fmt::{self, Write};
-use crate::input::{Bytes, Input};
+use crate::input::{Bytes, Input, Span};
+use crate::util::line::{char_count, for_each_char, line_containing, line_count, lines, Line};
 
+use super::style::Style;
+use super::unit::unicode_width;
 use super::{DisplayBase, InputDisplay, PreferredFormat};
 
 const DEFAULT_MAX_WIDTH: usize = 80;
+const DEFAULT_SNIPPET_CONTEXT_LINES: usize = 1;
 const INVALID_SPAN_ERROR: &str = "\
 note: error span is not within the error input indicating the
       concrete error being used has a bug. Consider raising an
@@ -17,6 +23,9 @@ note: error span is not within the error input indicating the
 pub struct ErrorDisplay<'a, T> {
     error: &'a T,
     banner: bool,
+    color: bool,
+    snippet: bool,
+    snippet_context_lines: usize,
     format: PreferredFormat,
     input_max_width: usize,
 }
@@ -36,6 +45,9 @@ where
             error,
             format,
             banner: false,
+            color: false,
+            snippet: false,
+            snippet_context_lines: DEFAULT_SNIPPET_CONTEXT_LINES,
             input_max_width: DEFAULT_MAX_WIDTH,
         }
     }
@@ -55,6 +67,59 @@ where
         self
     }
 
+    /// Set whether or not ANSI colours should be written.
+    ///
+    /// When enabled, the banner, the input underline, expected values and the
+    /// backtrace operations are coloured.
+    #[cfg(feature = "color")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+    pub fn color(mut self, value: bool) -> Self {
+        self.color = value;
+        self
+    }
+
+    /// Set whether or not the input should be rendered as a source snippet.
+    ///
+    /// Instead of a single line of input, the lines surrounding the error are
+    /// written with line numbers, the error span underlined with `^` and the
+    /// span of each context in the backtrace that differs underlined with `-`.
+    /// This is best suited to multi-line text input such as config files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Expected, Input};
+    ///
+    /// let input = dangerous::input("a = 1\nb = x\n");
+    /// let error: Expected = input
+    ///     .read_all(|r| {
+    ///         r.skip_until_consume('\n')?;
+    ///         r.skip_until('x')?;
+    ///         r.consume('2')?;
+    ///         r.consume('\n')
+    ///     })
+    ///     .unwrap_err();
+    ///
+    /// let formatted = error.display().snippet(true).to_string();
+    ///
+    /// assert!(formatted.contains(" --> 2:5\n"));
+    /// assert!(formatted.contains("2 | b = x\n"));
+    /// assert!(formatted.contains("  |     ^ expected exact value\n"));
+    /// ```
+    pub fn snippet(mut self, value: bool) -> Self {
+        self.snippet = value;
+        self
+    }
+
+    /// Set the number of lines to show before and after the error in a source
+    /// snippet.
+    ///
+    /// Defaults to `1`.
+    pub fn snippet_context_lines(mut self, value: usize) -> Self {
+        self.snippet_context_lines = value;
+        self
+    }
+
     /// Set the `max-width` for wrapping error output.
     pub fn input_max_width(mut self, value: usize) -> Self {
         self.input_max_width = value;
@@ -90,40 +155,51 @@ where
         let input_display = self.configure_input_display(input.display());
         let input = input.into_bytes();
         if let Some(expected_value) = self.error.expected() {
-            let expected_display = self.configure_input_display(expected_value.display());
-            w.write_str("expected:\n")?;
-            write_input(w, expected_display, false)?;
-            w.write_str("in:\n")?;
+            if expected_value.is_any_of() {
+                w.write_str("expected any of:\n> ")?;
+            } else if expected_value.is_ignore_ascii_case() {
+                w.write_str("expected (ignoring ASCII case):\n> ")?;
+            } else {
+                w.write_str("expected:\n> ")?;
+            }
+            Style::Expected.write(w, self.color, |w| {
+                expected_value.write_display(w, &|display| self.configure_expected_display(display))
+            })?;
+            w.write_str("\nin:\n")?;
         }
-        if root.span.is_within(input.span()) {
-            write_input(w, input_display.span(root.span, self.input_max_width), true)?;
+        if let (true, Some(span_range)) = (self.snippet, root.span.range_of(input.span())) {
+            self.write_snippet(w, input.as_dangerous(), span_range)?;
         } else {
-            w.write_str(INVALID_SPAN_ERROR)?;
-            w.write_str("input:\n")?;
-            write_input(w, input_display, false)?;
-        }
-        // Write additional
-        w.write_str("additional:\n  ")?;
-        if let Some(span_range) = root.span.range_of(input.span()) {
-            if matches!(
-                self.format,
-                PreferredFormat::Str | PreferredFormat::StrCjk | PreferredFormat::BytesAscii
-            ) {
-                w.write_str("error line: ")?;
-                w.write_usize(line_offset(&input, span_range.start))?;
-                w.write_str(", ")?;
+            if root.span.is_within(input.span()) {
+                write_input(w, input_display.span(root.span, self.input_max_width), true)?;
+            } else {
+                w.write_str(INVALID_SPAN_ERROR)?;
+                w.write_str("input:\n")?;
+                write_input(w, input_display, false)?;
             }
-            w.write_str("error offset: ")?;
-            w.write_usize(span_range.start)?;
-            w.write_str(", input length: ")?;
-            w.write_usize(input.len())?;
-        } else {
-            w.write_str("error: ")?;
-            DisplayBase::fmt(&root.span, w)?;
-            w.write_str("input: ")?;
-            DisplayBase::fmt(&input.span(), w)?;
+            // Write additional
+            w.write_str("additional:\n  ")?;
+            if let Some(span_range) = root.span.range_of(input.span()) {
+                if matches!(
+                    self.format,
+                    PreferredFormat::Str | PreferredFormat::StrCjk | PreferredFormat::BytesAscii
+                ) {
+                    w.write_str("error line: ")?;
+                    w.write_usize(line_offset(&input, span_range.start))?;
+                    w.write_str(", ")?;
+                }
+                w.write_str("error offset: ")?;
+                w.write_usize(span_range.start)?;
+                w.write_str(", input length: ")?;
+                w.write_usize(input.len())?;
+            } else {
+                w.write_str("error: ")?;
+                DisplayBase::fmt(&root.span, w)?;
+                w.write_str("input: ")?;
+                DisplayBase::fmt(&input.span(), w)?;
+            }
+            w.write_char('\n')?;
         }
-        w.write_char('\n')?;
         // Write context backtrace
         w.write_str("backtrace:")?;
         let mut child_index = 1;
@@ -141,11 +217,11 @@ where
                     w.write_usize(parent_depth)?;
                 }
                 w.write_str(". `")?;
-                context.operation().description(w)?;
+                Style::Operation.write(w, self.color, |w| context.operation().description(w))?;
                 w.write_char('`')?;
                 if context.has_expected() {
                     w.write_str(" (expected ")?;
-                    context.expected(w)?;
+                    Style::Expected.write(w, self.color, |w| context.expected(w))?;
                     w.write_char(')')?;
                 }
                 fmt::Result::Ok(())
@@ -159,9 +235,114 @@ where
         }
     }
 
+    fn configure_expected_display<'b>(&self, display: InputDisplay<'b>) -> InputDisplay<'b> {
+        let display = self.configure_input_display(display);
+        if self.format == PreferredFormat::HexDump {
+            // Expected values are written on a single line.
+            display.format(PreferredFormat::BytesAscii)
+        } else {
+            display
+        }
+    }
+
     fn configure_input_display<'b>(&self, display: InputDisplay<'b>) -> InputDisplay<'b> {
+        #[cfg(feature = "color")]
+        let display = display.color(self.color);
         display.format(self.format)
     }
+
+    fn write_snippet(&self, w: &mut dyn Write, input: &[u8], span: Range<usize>) -> fmt::Result {
+        let cjk = self.format == PreferredFormat::StrCjk;
+        let (start_line, start_line_offset) = line_containing(input, span.start);
+        let (end_line, _) = line_containing(input, span.end.max(span.start + 1) - 1);
+        let first_line = start_line.saturating_sub(self.snippet_context_lines).max(1);
+        let last_line = end_line.saturating_add(self.snippet_context_lines);
+        let gutter_width = digit_count(last_line.min(line_count(input)));
+        // Write location
+        write_repeated(w, ' ', gutter_width)?;
+        Style::Gutter.write(w, self.color, |w| w.write_str("--> "))?;
+        w.write_usize(start_line)?;
+        w.write_char(':')?;
+        w.write_usize(char_count(&input[start_line_offset..span.start]) + 1)?;
+        w.write_char('\n')?;
+        write_gutter(w, None, gutter_width, self.color)?;
+        w.write_char('\n')?;
+        // Write lines
+        let root = self.error.backtrace().root();
+        let mut elided = false;
+        for line in lines(input) {
+            if line.number < first_line
+                || (line.number > start_line + 2 && line.number + 2 < end_line)
+            {
+                if line.number > start_line && !elided {
+                    elided = true;
+                    w.write_str("...\n")?;
+                }
+                continue;
+            }
+            if line.number > last_line {
+                break;
+            }
+            write_gutter(w, Some(line.number), gutter_width, self.color)?;
+            if line.end > line.start {
+                w.write_char(' ')?;
+                write_text(w, &input[line.start..line.end], cjk)?;
+            }
+            w.write_char('\n')?;
+            // Underline the error span on each line it covers.
+            if line.number >= start_line && line.number <= end_line {
+                let marks = SpanMarks::new(input, &line, span.clone(), cjk);
+                if line.number == start_line || !marks.is_empty() {
+                    write_gutter(w, None, gutter_width, self.color)?;
+                    Style::Underline.write(w, self.color, |w| {
+                        marks.write('^', w)?;
+                        if line.number == start_line {
+                            w.write_char(' ')?;
+                            if root.has_expected() {
+                                w.write_str("expected ")?;
+                                root.expected(w)?;
+                            } else {
+                                self.error.description(w)?;
+                            }
+                        }
+                        Ok(())
+                    })?;
+                    w.write_char('\n')?;
+                }
+            }
+            // Label the span of each context starting on this line.
+            let input_span = Span::from(input);
+            let write_success = self.error.backtrace().walk(&mut |_, context| {
+                let range = match context.span() {
+                    Some(span) if span != root.span && span != input_span => {
+                        match span.range_of(input_span) {
+                            Some(range) => range,
+                            None => return true,
+                        }
+                    }
+                    _ => return true,
+                };
+                if range.start < line.start || range.start > line.end {
+                    return true;
+                }
+                let write = || {
+                    write_gutter(w, None, gutter_width, self.color)?;
+                    Style::Operation.write(w, self.color, |w| {
+                        SpanMarks::new(input, &line, range, cjk).write('-', w)?;
+                        w.write_char(' ')?;
+                        context.operation().description(w)
+                    })?;
+                    w.write_char('\n')
+                };
+                write().is_ok()
+            });
+            if !write_success {
+                return Err(fmt::Error);
+            }
+        }
+        write_gutter(w, None, gutter_width, self.color)?;
+        w.write_char('\n')
+    }
 }
 
 impl<'a, 'i, T> fmt::DisplayBase for ErrorDisplay<'a, T>
@@ -170,9 +351,17 @@ where
 {
     fn fmt(&self, w: &mut dyn Write) -> fmt::Result {
         if self.banner {
-            w.write_str("\n-- INPUT ERROR ---------------------------------------------\n")?;
+            w.write_char('\n')?;
+            Style::Banner.write(w, self.color, |w| {
+                w.write_str("-- INPUT ERROR ---------------------------------------------")
+            })?;
+            w.write_char('\n')?;
             self.write_sections(w)?;
-            w.write_str("\n------------------------------------------------------------\n")
+            w.write_char('\n')?;
+            Style::Banner.write(w, self.color, |w| {
+                w.write_str("------------------------------------------------------------")
+            })?;
+            w.write_char('\n')
         } else {
             self.write_sections(w)
         }
@@ -206,7 +395,106 @@ fn line_offset(input: &Bytes<'_>, span_offset: usize) -> usize {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Snippet support
+
+fn digit_count(mut value: usize) -> usize {
+    let mut count = 1;
+    while value >= 10 {
+        value /= 10;
+        count += 1;
+    }
+    count
+}
+
+/// Returns the display width of text written with [`write_text()`].
+fn text_width(bytes: &[u8], cjk: bool) -> usize {
+    let mut width = 0;
+    for_each_char(bytes, |c| width += char_width(c, cjk));
+    width
+}
+
+fn write_text(w: &mut dyn Write, bytes: &[u8], cjk: bool) -> fmt::Result {
+    let mut result = Ok(());
+    for_each_char(bytes, |c| {
+        if result.is_ok() {
+            result = match c {
+                '\t' => write_repeated(w, ' ', char_width(c, cjk)),
+                c if c.is_control() => w.write_char(char::REPLACEMENT_CHARACTER),
+                c => w.write_char(c),
+            };
+        }
+    });
+    result
+}
+
+fn char_width(c: char, cjk: bool) -> usize {
+    match c {
+        '\t' => 4,
+        c if c.is_control() => 1,
+        c => unicode_width(c, cjk),
+    }
+}
+
+fn write_gutter(
+    w: &mut dyn Write,
+    number: Option<usize>,
+    width: usize,
+    color: bool,
+) -> fmt::Result {
+    Style::Gutter.write(w, color, |w| {
+        match number {
+            Some(number) => {
+                write_repeated(w, ' ', width - digit_count(number))?;
+                w.write_usize(number)?;
+            }
+            None => write_repeated(w, ' ', width)?,
+        }
+        w.write_str(" |")
+    })
+}
+
+fn write_repeated(w: &mut dyn Write, c: char, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        w.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// The columns of a span within a line.
+struct SpanMarks {
+    offset: usize,
+    width: usize,
+}
+
+impl SpanMarks {
+    fn new(input: &[u8], line: &Line, span: Range<usize>, cjk: bool) -> Self {
+        let start = span.start.clamp(line.start, line.end);
+        let end = span.end.clamp(start, line.end);
+        Self {
+            offset: text_width(&input[line.start..start], cjk),
+            width: text_width(&input[start..end], cjk),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.width == 0
+    }
+
+    fn write(&self, mark: char, w: &mut dyn Write) -> fmt::Result {
+        write_repeated(w, ' ', self.offset + 1)?;
+        write_repeated(w, mark, self.width.max(1))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
 fn write_input(w: &mut dyn Write, input: InputDisplay<'_>, underline: bool) -> fmt::Result {
+    if input.is_multi_line() {
+        let input = if underline { input.underline() } else { input };
+        fmt::DisplayBase::fmt(&input, w)?;
+        return w.write_char('\n');
+    }
     let input = input.prepare();
     w.write_str("> ")?;
     fmt::DisplayBase::fmt(&input, w)?;
@@ -2,8 +2,12 @@ use crate::fmt::{self, Write};
 use crate::input::{Input, PrivateExt, Span};
 
 use super::section::{Section, SectionOpt};
+use super::style::Style;
 use super::unit::{byte_display_width, byte_display_write, char_display_width, char_display_write};
 
+pub(super) const HEX_ROW_LEN: usize = 16;
+const HEX_ROW_HALF_LEN: usize = HEX_ROW_LEN / 2;
+const HEX_OFFSET_DIGITS: usize = 8;
 const DEFAULT_SECTION_OPTION: SectionOpt = SectionOpt::HeadTail { width: 1024 };
 
 /// Preferred [`Input`] formats.
@@ -18,6 +22,30 @@ pub enum PreferredFormat {
     Bytes,
     /// Prefer displaying as bytes with valid ASCII graphic characters.
     BytesAscii,
+    /// Prefer displaying as a hex dump of rows of bytes, with the offset of
+    /// each row and the bytes as valid ASCII graphic characters.
+    ///
+    /// The width of a hex dump is the number of bytes to show, rounded to
+    /// whole rows of 16 bytes. When underlined, a row marking the span is
+    /// written beneath each row of bytes the span covers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    /// use dangerous::display::PreferredFormat;
+    ///
+    /// let formatted = dangerous::input(b"hello world\x00\xff")
+    ///     .display()
+    ///     .format(PreferredFormat::HexDump)
+    ///     .to_string();
+    ///
+    /// assert_eq!(
+    ///     formatted,
+    ///     "00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 00 ff           |hello world..|"
+    /// );
+    /// ```
+    HexDump,
 }
 
 /// Provides configurable [`Input`] formatting.
@@ -50,6 +78,7 @@ pub enum PreferredFormat {
 pub struct InputDisplay<'i> {
     input: &'i [u8],
     underline: bool,
+    color: bool,
     format: PreferredFormat,
     section: Option<Section<'i>>,
     section_opt: SectionOpt,
@@ -67,6 +96,7 @@ impl<'i> InputDisplay<'i> {
             input,
             format: PreferredFormat::Bytes,
             underline: false,
+            color: false,
             section: None,
             section_opt: DEFAULT_SECTION_OPTION,
         }
@@ -90,6 +120,16 @@ impl<'i> InputDisplay<'i> {
         self
     }
 
+    /// Set whether or not ANSI colours should be written.
+    ///
+    /// When enabled, the input underline is coloured.
+    #[cfg(feature = "color")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "color")))]
+    pub fn color(mut self, value: bool) -> Self {
+        self.color = value;
+        self
+    }
+
     /// Hint to the formatter that the [`Input`] is a UTF-8 `str`.
     pub fn str_hint(self) -> Self {
         match self.format {
@@ -199,6 +239,11 @@ impl<'i> InputDisplay<'i> {
         self
     }
 
+    /// Returns `true` if the input is written over multiple lines.
+    pub(super) fn is_multi_line(&self) -> bool {
+        self.format == PreferredFormat::HexDump
+    }
+
     /// Compute the sections of input to display.
     pub fn prepare(mut self) -> Self {
         let computed = self.section_opt.compute(self.input, self.format);
@@ -211,7 +256,7 @@ impl<'i> fmt::DisplayBase for InputDisplay<'i> {
     fn fmt(&self, w: &mut dyn Write) -> fmt::Result {
         match &self.section {
             None => self.clone().prepare().fmt(w),
-            Some(section) => section.write(w, self.underline),
+            Some(section) => section.write(w, self.underline, self.color),
         }
     }
 }
@@ -233,6 +278,7 @@ impl<'i> fmt::Display for InputDisplay<'i> {
 pub(super) struct InputWriter<'a> {
     w: &'a mut dyn Write,
     underline: bool,
+    color: bool,
     full: &'a [u8],
     span: Option<Span>,
 }
@@ -243,10 +289,12 @@ impl<'a> InputWriter<'a> {
         full: &'a [u8],
         span: Option<Span>,
         underline: bool,
+        color: bool,
     ) -> Self {
         Self {
             w,
             underline,
+            color,
             full,
             span,
         }
@@ -391,6 +439,93 @@ impl<'a> InputWriter<'a> {
         Ok(())
     }
 
+    ///////////////////////////////////////////////////////////////////////////
+    // Hex dump
+
+    pub(super) fn write_hex_dump(&mut self, rows: &[u8]) -> fmt::Result {
+        let has_more_before = has_more_before(rows, self.full);
+        if has_more_before {
+            self.w.write_str("..")?;
+        }
+        self.write_hex_rows(rows, has_more_before)?;
+        if has_more_after(rows, self.full) {
+            self.w.write_str("\n..")?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn write_hex_dump_pair(&mut self, head: &[u8], tail: &[u8]) -> fmt::Result {
+        self.write_hex_rows(head, false)?;
+        self.w.write_str("\n..")?;
+        self.write_hex_rows(tail, true)
+    }
+
+    fn write_hex_rows(&mut self, rows: &[u8], mut line_break: bool) -> fmt::Result {
+        let full = Span::from(self.full);
+        let start = Span::from(rows)
+            .range_of(full)
+            .map_or(0, |range| range.start);
+        let span = self.span.and_then(|span| span.range_of(full));
+        if rows.is_empty() {
+            return self.write_hex_row(start, rows);
+        }
+        for (i, row) in rows.chunks(HEX_ROW_LEN).enumerate() {
+            if line_break {
+                self.w.write_char('\n')?;
+            }
+            line_break = true;
+            let offset = start + i * HEX_ROW_LEN;
+            self.write_hex_row(offset, row)?;
+            if let (true, Some(span)) = (self.underline, span.clone()) {
+                let is_marked = |index: usize| {
+                    let offset = offset + index;
+                    span.contains(&offset) || (span.is_empty() && span.start == offset)
+                };
+                if (0..=row.len()).any(is_marked) {
+                    self.w.write_char('\n')?;
+                    let row_len = row.len();
+                    let color = self.color;
+                    Style::Underline.write(self.w, color, |w| {
+                        write_hex_row_underline(w, row_len, is_marked)
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_hex_row(&mut self, offset: usize, row: &[u8]) -> fmt::Result {
+        let mut digits = HEX_OFFSET_DIGITS;
+        while digits < usize::BITS as usize / 4 && offset >> (digits * 4) != 0 {
+            digits += 2;
+        }
+        for i in (0..digits / 2).rev() {
+            #[allow(clippy::cast_possible_truncation)]
+            let byte = (offset >> (i * 8)) as u8;
+            self.w.write_hex(byte)?;
+        }
+        self.w.write_char(' ')?;
+        for i in 0..HEX_ROW_LEN {
+            if i % HEX_ROW_HALF_LEN == 0 {
+                self.w.write_char(' ')?;
+            }
+            match row.get(i) {
+                Some(&b) => self.w.write_hex(b)?,
+                None => self.write_space(2)?,
+            }
+            self.w.write_char(' ')?;
+        }
+        self.w.write_str(" |")?;
+        for &b in row {
+            if b == b' ' || b.is_ascii_graphic() {
+                self.w.write_char(b as char)?;
+            } else {
+                self.w.write_char('.')?;
+            }
+        }
+        self.w.write_char('|')
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // Private
 
@@ -434,6 +569,42 @@ impl<'a> InputWriter<'a> {
     }
 }
 
+fn write_hex_row_underline(
+    w: &mut dyn Write,
+    row_len: usize,
+    is_marked: impl Fn(usize) -> bool,
+) -> fmt::Result {
+    // Spaces are only written before a mark, so there is no trailing space.
+    fn write_mark(w: &mut dyn Write, pending_spaces: &mut usize, mark: &str) -> fmt::Result {
+        for _ in 0..*pending_spaces {
+            w.write_char(' ')?;
+        }
+        *pending_spaces = 0;
+        w.write_str(mark)
+    }
+    let mut pending_spaces = HEX_OFFSET_DIGITS + 1;
+    for i in 0..HEX_ROW_LEN {
+        if i % HEX_ROW_HALF_LEN == 0 {
+            pending_spaces += 1;
+        }
+        if is_marked(i) {
+            write_mark(w, &mut pending_spaces, "^^")?;
+        } else {
+            pending_spaces += 2;
+        }
+        pending_spaces += 1;
+    }
+    pending_spaces += 2;
+    for i in 0..row_len {
+        if is_marked(i) {
+            write_mark(w, &mut pending_spaces, "^")?;
+        } else {
+            pending_spaces += 1;
+        }
+    }
+    Ok(())
+}
+
 fn has_more_before(bytes: &[u8], full: &[u8]) -> bool {
     Span::from(full).is_overlapping_start_of(bytes.into())
 }
@@ -0,0 +1,226 @@
+use crate::error::{self, Context};
+use crate::fmt::{self, Write};
+use crate::input::{Input, Span};
+use crate::util::line::{char_count, line_containing};
+
+use super::{DisplayBase, PreferredFormat};
+
+/// Provides machine-readable JSON formatting of [`error::Details`].
+///
+/// The document is written on a single line and contains the following:
+///
+/// - `operation`: the description of the root operation that failed.
+/// - `description`: the description of what went wrong.
+/// - `expected`: the expected value formatted as it would be within an
+///   [`ErrorDisplay`], or `null`.
+/// - `input`: the `length` of the input and whether it `is_string`.
+/// - `span`: the `start` and `end` byte offsets of the error within the input.
+/// - `start` and `end`: the `line` and `column` of the span within the input.
+///   Both are one-based, with columns counted in `char`s.
+/// - `backtrace`: each context walked with its parent `depth`, `operation`,
+///   `expected` value and `span` within the input.
+///
+/// Spans and positions are `null` if they are not within the input.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Expected, Input};
+/// use dangerous::display::ErrorJson;
+///
+/// let error: Expected = dangerous::input(b"hello")
+///     .read_all(|r| r.consume(b"world"))
+///     .unwrap_err();
+///
+/// let json = ErrorJson::new(&error).to_string();
+///
+/// assert!(json.starts_with(r#"{"operation":"consume input","#));
+/// assert!(json.contains(r#""span":{"start":0,"end":5}"#));
+/// assert!(json.contains(r#""start":{"line":1,"column":1}"#));
+/// ```
+///
+/// [`ErrorDisplay`]: crate::display::ErrorDisplay
+#[derive(Clone)]
+#[must_use = "error displays must be written"]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct ErrorJson<'a, T> {
+    error: &'a T,
+}
+
+impl<'a, 'i, T> ErrorJson<'a, T>
+where
+    T: error::Details<'i>,
+{
+    /// Create a new `ErrorJson` given [`error::Details`].
+    pub fn new(error: &'a T) -> Self {
+        Self { error }
+    }
+
+    fn write_expected(&self, w: &mut dyn Write, is_string: bool) -> fmt::Result {
+        match self.error.expected() {
+            Some(value) => write_string(w, |w| {
+                value.write_display(w, &|display| {
+                    if is_string {
+                        display.format(PreferredFormat::Str)
+                    } else {
+                        display
+                    }
+                })
+            }),
+            None => w.write_str("null"),
+        }
+    }
+}
+
+impl<'a, 'i, T> DisplayBase for ErrorJson<'a, T>
+where
+    T: error::Details<'i>,
+{
+    fn fmt(&self, w: &mut dyn Write) -> fmt::Result {
+        let input = self.error.input();
+        let is_string = input.is_string();
+        let input = input.into_bytes();
+        let bytes = input.as_dangerous();
+        let root = self.error.backtrace().root();
+        let root_range = root.span.range_of(input.span());
+        w.write_str("{\"operation\":")?;
+        write_string(w, |w| root.operation().description(w))?;
+        w.write_str(",\"description\":")?;
+        write_string(w, |w| self.error.description(w))?;
+        w.write_str(",\"expected\":")?;
+        self.write_expected(w, is_string)?;
+        w.write_str(",\"input\":{\"length\":")?;
+        w.write_usize(bytes.len())?;
+        w.write_str(",\"is_string\":")?;
+        w.write_str(if is_string { "true" } else { "false" })?;
+        w.write_str("},\"span\":")?;
+        write_span(w, root.span, input.span())?;
+        w.write_str(",\"start\":")?;
+        write_position(w, bytes, root_range.as_ref().map(|range| range.start))?;
+        w.write_str(",\"end\":")?;
+        write_position(w, bytes, root_range.as_ref().map(|range| range.end))?;
+        w.write_str(",\"backtrace\":[")?;
+        let mut first = true;
+        let write_success = self.error.backtrace().walk(&mut |parent_depth, context| {
+            let mut write = || {
+                if !first {
+                    w.write_char(',')?;
+                }
+                first = false;
+                write_context(w, parent_depth, context, input.span())
+            };
+            write().is_ok()
+        });
+        if !write_success {
+            return Err(fmt::Error);
+        }
+        w.write_str("]}")
+    }
+}
+
+impl<'a, 'i, T> fmt::Debug for ErrorJson<'a, T>
+where
+    T: error::Details<'i>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayBase::fmt(self, f)
+    }
+}
+
+impl<'a, 'i, T> fmt::Display for ErrorJson<'a, T>
+where
+    T: error::Details<'i>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayBase::fmt(self, f)
+    }
+}
+
+fn write_context(
+    w: &mut dyn Write,
+    parent_depth: usize,
+    context: &dyn Context,
+    input_span: Span,
+) -> fmt::Result {
+    w.write_str("{\"depth\":")?;
+    w.write_usize(parent_depth)?;
+    w.write_str(",\"operation\":")?;
+    write_string(w, |w| context.operation().description(w))?;
+    w.write_str(",\"expected\":")?;
+    if context.has_expected() {
+        write_string(w, |w| context.expected(w))?;
+    } else {
+        w.write_str("null")?;
+    }
+    w.write_str(",\"span\":")?;
+    match context.span() {
+        Some(span) => write_span(w, span, input_span)?,
+        None => w.write_str("null")?,
+    }
+    w.write_char('}')
+}
+
+fn write_span(w: &mut dyn Write, span: Span, input_span: Span) -> fmt::Result {
+    match span.range_of(input_span) {
+        Some(range) => {
+            w.write_str("{\"start\":")?;
+            w.write_usize(range.start)?;
+            w.write_str(",\"end\":")?;
+            w.write_usize(range.end)?;
+            w.write_char('}')
+        }
+        None => w.write_str("null"),
+    }
+}
+
+fn write_position(w: &mut dyn Write, input: &[u8], offset: Option<usize>) -> fmt::Result {
+    match offset {
+        Some(offset) => {
+            let (line, line_start) = line_containing(input, offset);
+            w.write_str("{\"line\":")?;
+            w.write_usize(line)?;
+            w.write_str(",\"column\":")?;
+            w.write_usize(char_count(&input[line_start..offset]) + 1)?;
+            w.write_char('}')
+        }
+        None => w.write_str("null"),
+    }
+}
+
+fn write_string(w: &mut dyn Write, f: impl FnOnce(&mut dyn Write) -> fmt::Result) -> fmt::Result {
+    w.write_char('"')?;
+    f(&mut StringWriter(w))?;
+    w.write_char('"')
+}
+
+/// Escapes everything written as the contents of a JSON string.
+struct StringWriter<'a>(&'a mut dyn Write);
+
+impl<'a> Write for StringWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match c {
+            '"' => self.0.write_str("\\\""),
+            '\\' => self.0.write_str("\\\\"),
+            '\n' => self.0.write_str("\\n"),
+            '\r' => self.0.write_str("\\r"),
+            '\t' => self.0.write_str("\\t"),
+            #[allow(clippy::cast_possible_truncation)]
+            c if (c as u32) < 0x20 => {
+                self.0.write_str("\\u00")?;
+                self.0.write_hex(c as u8)
+            }
+            c => self.0.write_char(c),
+        }
+    }
+
+    fn write_usize(&mut self, v: usize) -> fmt::Result {
+        self.0.write_usize(v)
+    }
+}
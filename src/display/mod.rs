@@ -2,13 +2,18 @@
 
 mod error;
 mod input;
+#[cfg(feature = "json")]
+mod json;
 mod section;
+mod style;
 mod unit;
 
 use core::fmt::{Formatter, Result};
 
 pub use self::error::ErrorDisplay;
 pub use self::input::{InputDisplay, PreferredFormat};
+#[cfg(feature = "json")]
+pub use self::json::ErrorJson;
 
 /// Library specific display trait that accepts a [`Write`] without requiring a
 /// formatter.
@@ -4,6 +4,9 @@
 // | tail      | `.. "a"`     | `[.. 97]`    | `[.. 'a']`     |
 // | head-tail | `"a" .. "a"` | `[97 .. 97]` | `['a' .. 'a']` |
 // | span      | `.. "a" ..`  | `[.. 97 ..]` | `[.. 'a' ..]`  |
+//
+// A hex dump is written as rows of bytes, where the width is the number of
+// bytes to show rounded to whole rows.
 
 use core::str;
 
@@ -11,7 +14,8 @@ use crate::fmt::{self, Write};
 use crate::input::Span;
 use crate::util::utf8;
 
-use super::input::{InputWriter, PreferredFormat};
+use super::input::{InputWriter, PreferredFormat, HEX_ROW_LEN};
+use super::style::Style;
 use super::unit::UnitIter;
 
 const MIN_WIDTH: usize = 16;
@@ -61,6 +65,10 @@ enum Visible<'a> {
     BytesPair(&'a [u8], &'a [u8]),
     // head-tail-bytes-ascii
     BytesAsciiPair(&'a [u8], &'a [u8]),
+    // head-hex-dump, tail-hex-dump, span-hex-dump
+    HexDump(&'a [u8]),
+    // head-tail-hex-dump
+    HexDumpPair(&'a [u8], &'a [u8]),
 }
 
 #[derive(Clone)]
@@ -89,6 +97,7 @@ impl<'a> Section<'a> {
                     Visible::BytesAscii(full)
                 }
             }
+            PreferredFormat::HexDump => Visible::HexDump(full),
         };
         Self {
             full,
@@ -104,6 +113,7 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_head(full, width, true),
             PreferredFormat::Str => take_str_head(full, width, false),
             PreferredFormat::StrCjk => take_str_head(full, width, true),
+            PreferredFormat::HexDump => take_hex_head(full, width),
         };
         Self {
             full,
@@ -119,6 +129,7 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_tail(full, width, true),
             PreferredFormat::Str => take_str_tail(full, width, false),
             PreferredFormat::StrCjk => take_str_tail(full, width, true),
+            PreferredFormat::HexDump => take_hex_tail(full, width),
         };
         Self {
             full,
@@ -134,6 +145,7 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_head_tail(full, width, true),
             PreferredFormat::Str => take_str_head_tail(full, width, false),
             PreferredFormat::StrCjk => take_str_head_tail(full, width, true),
+            PreferredFormat::HexDump => take_hex_head_tail(full, width),
         };
         Self {
             full,
@@ -161,6 +173,7 @@ impl<'a> Section<'a> {
                     PreferredFormat::BytesAscii => take_bytes_head(full, width, true),
                     PreferredFormat::Str => take_str_head(full, width, false),
                     PreferredFormat::StrCjk => take_str_head(full, width, true),
+                    PreferredFormat::HexDump => take_hex_head(full, width),
                 };
                 return Self {
                     full,
@@ -173,6 +186,7 @@ impl<'a> Section<'a> {
                     PreferredFormat::BytesAscii => take_bytes_tail(full, width, true),
                     PreferredFormat::Str => take_str_tail(full, width, false),
                     PreferredFormat::StrCjk => take_str_tail(full, width, true),
+                    PreferredFormat::HexDump => take_hex_tail(full, width),
                 };
                 return Self {
                     full,
@@ -199,6 +213,7 @@ impl<'a> Section<'a> {
             PreferredFormat::BytesAscii => take_bytes_span(full, span_offset, width, true),
             PreferredFormat::Str => take_str_span(full, span_offset, width, false),
             PreferredFormat::StrCjk => take_str_span(full, span_offset, width, true),
+            PreferredFormat::HexDump => take_hex_span(full, span_offset, width),
         };
         Self {
             full,
@@ -207,8 +222,22 @@ impl<'a> Section<'a> {
         }
     }
 
-    pub(super) fn write(&self, w: &mut dyn Write, underline: bool) -> fmt::Result {
-        let mut writer = InputWriter::new(w, self.full, self.span, underline);
+    pub(super) fn write(&self, w: &mut dyn Write, underline: bool, color: bool) -> fmt::Result {
+        let mut writer = InputWriter::new(w, self.full, self.span, underline, color);
+        match self.visible {
+            Visible::HexDump(rows) => return writer.write_hex_dump(rows),
+            Visible::HexDumpPair(head, tail) => return writer.write_hex_dump_pair(head, tail),
+            _ => {}
+        }
+        if underline {
+            Style::Underline.write(w, color, |w| self.write_line(w, true))
+        } else {
+            self.write_line(w, false)
+        }
+    }
+
+    fn write_line(&self, w: &mut dyn Write, underline: bool) -> fmt::Result {
+        let mut writer = InputWriter::new(w, self.full, self.span, underline, false);
         match self.visible {
             Visible::Bytes(bytes) => writer.write_bytes_side(bytes, false),
             Visible::BytesAscii(bytes) => writer.write_bytes_side(bytes, true),
@@ -218,6 +247,7 @@ impl<'a> Section<'a> {
             Visible::BytesAsciiPair(left, right) => writer.write_bytes_sides(left, right, true),
             Visible::StrPair(left, right) => writer.write_str_sides(left, right, false),
             Visible::StrCjkPair(left, right) => writer.write_str_sides(left, right, true),
+            Visible::HexDump(_) | Visible::HexDumpPair(..) => Ok(()),
         }
     }
 }
@@ -356,6 +386,53 @@ fn take_bytes_head_tail(bytes: &[u8], width: usize, show_ascii: bool) -> Visible
     }
 }
 
+fn hex_row_count(width: usize) -> usize {
+    // Undo the delimiter cost removed by `init_width()`, which a hex dump
+    // doesn't have.
+    ((width + DELIM_PAIR_COST) / HEX_ROW_LEN).max(1)
+}
+
+fn hex_rows(bytes: &[u8], start_row: usize, end_row: usize) -> &[u8] {
+    let start = (start_row * HEX_ROW_LEN).min(bytes.len());
+    let end = (end_row * HEX_ROW_LEN).min(bytes.len());
+    &bytes[start..end]
+}
+
+fn take_hex_head(bytes: &[u8], width: usize) -> Visible<'_> {
+    Visible::HexDump(hex_rows(bytes, 0, hex_row_count(width)))
+}
+
+fn take_hex_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let total_rows = (bytes.len() + HEX_ROW_LEN - 1) / HEX_ROW_LEN;
+    let start_row = total_rows.saturating_sub(hex_row_count(width));
+    Visible::HexDump(hex_rows(bytes, start_row, total_rows))
+}
+
+fn take_hex_head_tail(bytes: &[u8], width: usize) -> Visible<'_> {
+    let total_rows = (bytes.len() + HEX_ROW_LEN - 1) / HEX_ROW_LEN;
+    let rows = hex_row_count(width).max(2);
+    if total_rows <= rows {
+        Visible::HexDump(bytes)
+    } else {
+        let head_rows = (rows + 1) / 2;
+        let tail_start_row = total_rows - (rows - head_rows);
+        Visible::HexDumpPair(
+            hex_rows(bytes, 0, head_rows),
+            hex_rows(bytes, tail_start_row, total_rows),
+        )
+    }
+}
+
+fn take_hex_span(bytes: &[u8], span_offset: usize, width: usize) -> Visible<'_> {
+    let total_rows = (bytes.len() + HEX_ROW_LEN - 1) / HEX_ROW_LEN;
+    let rows = hex_row_count(width);
+    // Attempt to show 1/3 of the rows before the span.
+    let span_row = span_offset / HEX_ROW_LEN;
+    let end_row = (span_row.saturating_sub(rows / 3) + rows).min(total_rows);
+    let start_row = end_row.saturating_sub(rows);
+    Visible::HexDump(hex_rows(bytes, start_row, end_row))
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 /// Returns `Result<(length, remaining), ()>`
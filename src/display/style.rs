@@ -0,0 +1,44 @@
+use crate::fmt::{self, Write};
+
+const RESET: &str = "\x1b[0m";
+
+/// ANSI styles used when colour output is enabled.
+#[derive(Copy, Clone)]
+pub(super) enum Style {
+    /// The banner surrounding an error.
+    Banner,
+    /// The underline marking a span within input.
+    Underline,
+    /// An expected value.
+    Expected,
+    /// An operation within a backtrace.
+    Operation,
+    /// The line number gutter of a source snippet.
+    Gutter,
+}
+
+impl Style {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Banner | Self::Underline => "\x1b[1;31m",
+            Self::Expected => "\x1b[32m",
+            Self::Operation => "\x1b[36m",
+            Self::Gutter => "\x1b[1;34m",
+        }
+    }
+
+    /// Writes the output of `f` in this style if `enabled`, otherwise writes
+    /// it plainly.
+    pub(super) fn write<F>(self, w: &mut dyn Write, enabled: bool, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut dyn Write) -> fmt::Result,
+    {
+        if enabled {
+            w.write_str(self.code())?;
+            f(w)?;
+            w.write_str(RESET)
+        } else {
+            f(w)
+        }
+    }
+}
@@ -77,13 +77,13 @@ pub(super) fn char_display_write(c: char, w: &mut dyn Write) -> fmt::Result {
 
 #[cfg(feature = "unicode")]
 #[inline]
-fn unicode_width(c: char, cjk: bool) -> usize {
+pub(super) fn unicode_width(c: char, cjk: bool) -> usize {
     if cjk { c.width_cjk() } else { c.width() }.unwrap_or(1)
 }
 
 #[cfg(not(feature = "unicode"))]
 #[inline]
-fn unicode_width(_c: char, _cjk: bool) -> usize {
+pub(super) fn unicode_width(_c: char, _cjk: bool) -> usize {
     1
 }
 
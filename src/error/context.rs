@@ -1,7 +1,7 @@
 use core::any::Any;
 
 use crate::fmt;
-use crate::input::{Input, MaybeString, Span, Token, TokenType};
+use crate::input::{Endian, Input, MaybeString, Span, Token, TokenType};
 
 use super::WithContext;
 
@@ -134,6 +134,59 @@ where
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Item context
+
+/// A [`Context`] for an item read within a repetition.
+///
+/// See [`Reader::repeat()`] and friends.
+///
+/// [`Reader::repeat()`]: crate::Reader::repeat()
+#[non_exhaustive]
+#[derive(Copy, Clone)]
+pub struct ItemContext {
+    /// The section of input the item was read from.
+    pub span: Span,
+    /// The index of the item within the repetition, starting from `0`.
+    pub index: usize,
+}
+
+impl ItemContext {
+    pub(crate) fn new(span: Span, index: usize) -> Self {
+        Self { span, index }
+    }
+}
+
+impl Context for ItemContext {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn operation(&self) -> &dyn Operation {
+        self
+    }
+}
+
+impl Operation for ItemContext {
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("read item at index ")?;
+        w.write_usize(self.index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl fmt::Debug for ItemContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ItemContext")
+            .field("span", &self.span)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Core context
 
@@ -215,6 +268,7 @@ impl<'i> fmt::Debug for DebugFor<'i> {
 /// Core operations used by `dangerous`.
 #[allow(missing_docs)]
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CoreOperation {
     // Context
     Context,
@@ -223,8 +277,10 @@ pub enum CoreOperation {
     ReadPartial,
     // Consuming
     Consume,
+    ConsumeSuffix,
     // Skipping
     Skip,
+    SkipBits,
     SkipWhile,
     SkipStrWhile,
     SkipUntil,
@@ -237,17 +293,26 @@ pub enum CoreOperation {
     TakeArray,
     TakeUntil,
     TakeUntilConsume,
+    TakeUntilLast,
     TakeWhile,
     TakeConsumed,
+    TakeLengthPrefixed,
     TakeStrWhile,
     TakeRemainingStr,
+    TakeRegex,
+    TakeCaptures,
     // Peeking
     Peek,
     PeekByte,
     PeekChar,
+    PeekNum(CoreNumber),
+    PeekBits,
     // Reading
     ReadByte,
     ReadChar,
+    ReadNum(CoreNumber),
+    ReadBits,
+    ReadAsBits,
     // Errors
     RecoverIf,
     Verify,
@@ -261,7 +326,14 @@ pub enum CoreOperation {
 
 impl Operation for CoreOperation {
     fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        w.write_str(CoreOperation::description(*self))
+        w.write_str(CoreOperation::description(*self))?;
+        match *self {
+            Self::PeekNum(num) | Self::ReadNum(num) => {
+                w.write_char(' ')?;
+                fmt::DisplayBase::fmt(&num, w)
+            }
+            _ => Ok(()),
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -290,7 +362,9 @@ impl CoreOperation {
             Self::ReadAll => "read all input",
             Self::ReadPartial => "read a partial length of input",
             Self::Consume => "consume input",
+            Self::ConsumeSuffix => "consume input from the end",
             Self::Skip => "skip a length of input",
+            Self::SkipBits => "skip a number of bits",
             Self::SkipWhile => "skip input while a pattern matches",
             Self::SkipUntil => "skip input until a pattern matches",
             Self::SkipUntilConsume => "skip input until a pattern matches and consume it",
@@ -302,14 +376,23 @@ impl CoreOperation {
             Self::TakeWhile => "take input while a pattern matches",
             Self::TakeUntil => "take input until a pattern matches",
             Self::TakeUntilConsume => "take input until a pattern matches and consume it",
+            Self::TakeUntilLast => "take input until the last match of a pattern",
             Self::TakeConsumed => "take input that was consumed",
+            Self::TakeLengthPrefixed => "take a length prefixed input",
             Self::TakeStrWhile => "take UTF-8 input while a condition remains true",
             Self::TakeRemainingStr => "take remaining string within bytes",
+            Self::TakeRegex => "take input matching a regex",
+            Self::TakeCaptures => "take input captured by a regex",
             Self::Peek => "peek a length of input",
             Self::PeekByte => "peek a byte",
             Self::PeekChar => "peek a char",
+            Self::PeekNum(_) => "peek",
+            Self::PeekBits => "peek a number of bits",
             Self::ReadByte => "read a byte",
             Self::ReadChar => "read a char",
+            Self::ReadNum(_) => "read",
+            Self::ReadBits => "read a number of bits",
+            Self::ReadAsBits => "read input as bits",
             Self::RecoverIf => "recover if a condition returns true",
             Self::Verify => "read and verify input",
             Self::Expect => "read and expect a value",
@@ -327,8 +410,74 @@ impl fmt::DisplayBase for CoreOperation {
     }
 }
 
+/// Numbers read by `dangerous`.
+///
+/// Fixed width multi-byte numbers carry the [`Endian`] they were read with.
+#[allow(missing_docs)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoreNumber {
+    U8,
+    I8,
+    U16(Endian),
+    I16(Endian),
+    U32(Endian),
+    I32(Endian),
+    U64(Endian),
+    I64(Endian),
+    U128(Endian),
+    I128(Endian),
+    F32(Endian),
+    F64(Endian),
+    Uleb128U32,
+    Uleb128U64,
+    Sleb128I32,
+    Sleb128I64,
+    ZigZagI32,
+    ZigZagI64,
+}
+
+impl CoreNumber {
+    fn parts(self) -> (&'static str, Option<Endian>) {
+        match self {
+            Self::U8 => ("a u8", None),
+            Self::I8 => ("an i8", None),
+            Self::U16(endian) => ("a u16", Some(endian)),
+            Self::I16(endian) => ("an i16", Some(endian)),
+            Self::U32(endian) => ("a u32", Some(endian)),
+            Self::I32(endian) => ("an i32", Some(endian)),
+            Self::U64(endian) => ("a u64", Some(endian)),
+            Self::I64(endian) => ("an i64", Some(endian)),
+            Self::U128(endian) => ("a u128", Some(endian)),
+            Self::I128(endian) => ("an i128", Some(endian)),
+            Self::F32(endian) => ("an f32", Some(endian)),
+            Self::F64(endian) => ("an f64", Some(endian)),
+            Self::Uleb128U32 => ("a LEB128 u32", None),
+            Self::Uleb128U64 => ("a LEB128 u64", None),
+            Self::Sleb128I32 => ("a LEB128 i32", None),
+            Self::Sleb128I64 => ("a LEB128 i64", None),
+            Self::ZigZagI32 => ("a zigzag LEB128 i32", None),
+            Self::ZigZagI64 => ("a zigzag LEB128 i64", None),
+        }
+    }
+}
+
+impl fmt::DisplayBase for CoreNumber {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let (name, endian) = self.parts();
+        w.write_str(name)?;
+        if let Some(endian) = endian {
+            w.write_str(" (")?;
+            endian.fmt(w)?;
+            w.write_char(')')?;
+        }
+        Ok(())
+    }
+}
+
 /// Core expectations used by `dangerous`.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CoreExpected {
     /// What is expected is unknown.
     ///
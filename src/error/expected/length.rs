@@ -8,6 +8,9 @@ use crate::input::MaybeString;
 #[must_use = "error must be handled"]
 pub struct ExpectedLength<'i> {
     pub(crate) len: Length,
+    /// The length of input found, if more than the length of the context's
+    /// span, as for input spanning multiple segments.
+    pub(crate) found: Option<usize>,
     pub(crate) context: CoreContext,
     pub(crate) input: MaybeString<'i>,
 }
@@ -26,6 +29,16 @@ impl<'i> ExpectedLength<'i> {
         self.len
     }
 
+    /// The length of input found when the error occurred.
+    ///
+    /// This is the length of the context's span, unless the input spanned
+    /// multiple segments of a [`Chain`](crate::input::Chain).
+    #[must_use]
+    #[inline(always)]
+    pub fn found(&self) -> usize {
+        self.found.unwrap_or_else(|| self.context.span.len())
+    }
+
     /// The [`CoreContext`] around the error.
     #[must_use]
     #[inline(always)]
@@ -53,7 +66,7 @@ impl<'i> fmt::Debug for ExpectedLength<'i> {
 impl<'i> fmt::DisplayBase for ExpectedLength<'i> {
     fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
         w.write_str("found ")?;
-        byte_count(w, self.context.span.len())?;
+        byte_count(w, self.found())?;
         w.write_str(" when ")?;
         self.len.fmt(w)?;
         w.write_str(" was expected")
@@ -10,6 +10,8 @@ pub use self::value::ExpectedValue;
 use alloc::boxed::Box;
 
 use crate::display::ErrorDisplay;
+#[cfg(feature = "json")]
+use crate::display::ErrorJson;
 use crate::error::{
     Backtrace, BacktraceBuilder, Context, Details, RetryRequirement, ToRetryRequirement, Value,
     WithContext,
@@ -58,6 +60,13 @@ where
     pub fn display(&self) -> ErrorDisplay<'_, Self> {
         ErrorDisplay::new(self)
     }
+
+    /// Returns an `ErrorJson` for machine-readable formatting.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json(&self) -> ErrorJson<'_, Self> {
+        ErrorJson::new(self)
+    }
 }
 
 impl<'i, S> Expected<'i, S>
@@ -45,7 +45,9 @@ impl<'i> fmt::Debug for ExpectedValue<'i> {
 
 impl<'i> fmt::DisplayBase for ExpectedValue<'i> {
     fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        if self.is_fatal() {
+        if self.is_fatal() && self.expected().is_any_of() {
+            w.write_str("found none of the expected values")
+        } else if self.is_fatal() {
             w.write_str("found a different value to the exact expected")
         } else {
             w.write_str("not enough input to match expected value")
@@ -65,7 +67,8 @@ impl<'i> ToRetryRequirement for ExpectedValue<'i> {
         if self.is_fatal() {
             None
         } else {
-            let needed = self.expected().as_bytes().len();
+            // Any one byte or needle of a set is enough for a match.
+            let needed = self.expected().min_len();
             let had = self.context.span.len();
             RetryRequirement::from_had_and_needed(had, needed)
         }
@@ -79,7 +82,7 @@ impl<'i> ToRetryRequirement for ExpectedValue<'i> {
             return true;
         }
         match self.context.span.of(self.input.as_dangerous_bytes()) {
-            Some(found) => !self.expected().as_bytes().starts_with(found),
+            Some(found) => !self.expected().could_start_with(found),
             None => true,
         }
     }
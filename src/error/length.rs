@@ -3,6 +3,7 @@ use crate::fmt;
 
 /// Length that was expected in an operation.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[must_use]
 pub enum Length {
     /// A minimum length was expected.
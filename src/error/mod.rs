@@ -33,7 +33,8 @@ mod value;
 pub use self::backtrace::FullBacktrace;
 pub use self::backtrace::{Backtrace, BacktraceBuilder, BacktraceWalker, RootBacktrace};
 pub use self::context::{
-    Context, CoreContext, CoreExpected, CoreOperation, ExternalContext, Operation, WithChildContext,
+    Context, CoreContext, CoreExpected, CoreNumber, CoreOperation, ExternalContext, ItemContext,
+    Operation, WithChildContext,
 };
 pub use self::expected::{Expected, ExpectedLength, ExpectedValid, ExpectedValue};
 pub use self::fatal::Fatal;
@@ -10,6 +10,7 @@ use crate::fmt;
 /// result in a lot of wasted reprocessing of input if not handled correctly.
 #[must_use]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RetryRequirement(NonZeroUsize);
 
 impl RetryRequirement {
@@ -2,7 +2,9 @@ use core::slice;
 
 use crate::display::InputDisplay;
 use crate::fmt;
-use crate::input::{Bound, Bytes, Input};
+#[cfg(feature = "alloc")]
+use crate::input::Needles;
+use crate::input::{Bound, ByteSet, Bytes, IgnoreAsciiCase, Input};
 use crate::util::utf8::CharBytes;
 
 /// Value that was expected in an operation.
@@ -16,28 +18,129 @@ enum ValueInner<'i> {
     Char(CharBytes),
     Bytes(&'i [u8]),
     String(&'i str),
+    AnyOf(&'i [u8]),
+    BytesIgnoreAsciiCase(&'i [u8]),
+    StringIgnoreAsciiCase(&'i str),
+    #[cfg(feature = "alloc")]
+    Needles(&'i Needles),
 }
 
 impl<'i> Value<'i> {
     /// Returns the value as bytes.
+    ///
+    /// A set of [`Needles`](crate::input::Needles) has no single value, so is
+    /// returned as empty bytes. See [`Value::as_needles()`].
     #[must_use]
     pub fn as_bytes(&self) -> &[u8] {
         match &self.0 {
             ValueInner::Byte(v) => slice::from_ref(v),
             ValueInner::Char(v) => v.as_bytes(),
-            ValueInner::Bytes(v) => v,
-            ValueInner::String(v) => v.as_bytes(),
+            ValueInner::Bytes(v) | ValueInner::AnyOf(v) | ValueInner::BytesIgnoreAsciiCase(v) => v,
+            ValueInner::String(v) | ValueInner::StringIgnoreAsciiCase(v) => v.as_bytes(),
+            #[cfg(feature = "alloc")]
+            ValueInner::Needles(_) => &[],
+        }
+    }
+
+    /// Returns the [`Needles`] any one of which was expected, if the value is
+    /// a set of needles.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn as_needles(&self) -> Option<&'i Needles> {
+        match self.0 {
+            ValueInner::Needles(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if any one of the bytes, or needles, of the value was
+    /// expected, rather than all of them in order.
+    #[must_use]
+    pub fn is_any_of(&self) -> bool {
+        match self.0 {
+            ValueInner::AnyOf(_) => true,
+            #[cfg(feature = "alloc")]
+            ValueInner::Needles(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the value was expected ignoring ASCII case.
+    #[must_use]
+    pub fn is_ignore_ascii_case(&self) -> bool {
+        matches!(
+            self.0,
+            ValueInner::BytesIgnoreAsciiCase(_) | ValueInner::StringIgnoreAsciiCase(_)
+        )
+    }
+
+    /// Returns the least number of bytes that could match the value.
+    pub(crate) fn min_len(&self) -> usize {
+        match self.0 {
+            ValueInner::AnyOf(_) => 1,
+            #[cfg(feature = "alloc")]
+            ValueInner::Needles(v) => v.iter().map(<[u8]>::len).min().unwrap_or(0),
+            _ => self.as_bytes().len(),
+        }
+    }
+
+    /// Returns `true` if `found` could be the start of a match of the value.
+    pub(crate) fn could_start_with(&self, found: &[u8]) -> bool {
+        let expected = self.as_bytes();
+        if self.is_any_of() {
+            found.is_empty()
+        } else if self.is_ignore_ascii_case() {
+            expected.len() >= found.len() && expected[..found.len()].eq_ignore_ascii_case(found)
+        } else {
+            expected.starts_with(found)
         }
     }
 
     /// Returns an [`InputDisplay`] for formatting.
+    ///
+    /// A set of [`Needles`](crate::input::Needles) is displayed as empty, see
+    /// [`Value::as_needles()`].
     pub fn display(&self) -> InputDisplay<'_> {
         let display = Bytes::new(self.as_bytes(), Bound::StartEnd).display();
         match self.0 {
-            ValueInner::Byte(_) | ValueInner::Bytes(_) => display,
-            ValueInner::Char(_) | ValueInner::String(_) => display.str_hint(),
+            ValueInner::Byte(_)
+            | ValueInner::Bytes(_)
+            | ValueInner::AnyOf(_)
+            | ValueInner::BytesIgnoreAsciiCase(_) => display,
+            ValueInner::Char(_) | ValueInner::String(_) | ValueInner::StringIgnoreAsciiCase(_) => {
+                display.str_hint()
+            }
+            #[cfg(feature = "alloc")]
+            ValueInner::Needles(_) => display,
         }
     }
+
+    /// Writes the value with each display configured, writing each needle of
+    /// a set of needles separated by a comma.
+    pub(crate) fn write_display(
+        &self,
+        w: &mut dyn fmt::Write,
+        configure: &dyn Fn(InputDisplay<'_>) -> InputDisplay<'_>,
+    ) -> fmt::Result {
+        #[cfg(feature = "alloc")]
+        if let ValueInner::Needles(needles) = self.0 {
+            for (i, needle) in needles.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                let display = Bytes::new(needle, Bound::StartEnd).display();
+                let display = if needles.is_utf8() {
+                    display.str_hint()
+                } else {
+                    display
+                };
+                fmt::DisplayBase::fmt(&configure(display), w)?;
+            }
+            return Ok(());
+        }
+        fmt::DisplayBase::fmt(&configure(self.display()), w)
+    }
 }
 
 impl<'i> fmt::Debug for Value<'i> {
@@ -47,6 +150,11 @@ impl<'i> fmt::Debug for Value<'i> {
             ValueInner::Char(_) => "Char",
             ValueInner::Bytes(_) => "Bytes",
             ValueInner::String(_) => "String",
+            ValueInner::AnyOf(_) => "AnyOf",
+            ValueInner::BytesIgnoreAsciiCase(_) => "BytesIgnoreAsciiCase",
+            ValueInner::StringIgnoreAsciiCase(_) => "StringIgnoreAsciiCase",
+            #[cfg(feature = "alloc")]
+            ValueInner::Needles(v) => return f.debug_tuple("Needles").field(v).finish(),
         };
         let display = self.display().with_formatter(f);
         f.debug_tuple(name).field(&display).finish()
@@ -79,9 +187,45 @@ impl<'i> From<&'i str> for Value<'i> {
     }
 }
 
+impl<'a: 'i, 'i> From<ByteSet<'a>> for Value<'i> {
+    #[inline(always)]
+    fn from(v: ByteSet<'a>) -> Self {
+        Self(ValueInner::AnyOf(v.as_bytes()))
+    }
+}
+
+impl<'i> From<IgnoreAsciiCase<&'i [u8]>> for Value<'i> {
+    #[inline(always)]
+    fn from(v: IgnoreAsciiCase<&'i [u8]>) -> Self {
+        Self(ValueInner::BytesIgnoreAsciiCase(v.into_inner()))
+    }
+}
+
+impl<'i, const N: usize> From<IgnoreAsciiCase<&'i [u8; N]>> for Value<'i> {
+    #[inline(always)]
+    fn from(v: IgnoreAsciiCase<&'i [u8; N]>) -> Self {
+        Self(ValueInner::BytesIgnoreAsciiCase(v.into_inner()))
+    }
+}
+
+impl<'i> From<IgnoreAsciiCase<&'i str>> for Value<'i> {
+    #[inline(always)]
+    fn from(v: IgnoreAsciiCase<&'i str>) -> Self {
+        Self(ValueInner::StringIgnoreAsciiCase(v.into_inner()))
+    }
+}
+
 impl<'i, const N: usize> From<&'i [u8; N]> for Value<'i> {
     #[inline(always)]
     fn from(v: &'i [u8; N]) -> Self {
         Self(ValueInner::Bytes(v))
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<'a: 'i, 'i> From<&'a Needles> for Value<'i> {
+    #[inline(always)]
+    fn from(v: &'a Needles) -> Self {
+        Self(ValueInner::Needles(v))
+    }
+}
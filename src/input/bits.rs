@@ -0,0 +1,73 @@
+use crate::fmt;
+
+use super::Span;
+
+/// Order in which bits are read from each byte by a [`BitReader`].
+///
+/// [`BitReader`]: crate::BitReader
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BitOrder {
+    /// Bits are read from the most significant bit of each byte first, with
+    /// the first bit read being the most significant in the value.
+    ///
+    /// Used by formats such as H.264 and CAN.
+    MsbFirst,
+    /// Bits are read from the least significant bit of each byte first, with
+    /// the first bit read being the least significant in the value.
+    ///
+    /// Used by formats such as DEFLATE.
+    LsbFirst,
+}
+
+/// Range of bits within [`Input`].
+///
+/// Wraps the [`Span`] of bytes the bits were read from, along with the offset
+/// of the first bit within the first byte and the number of bits spanned.
+///
+/// [`Input`]: crate::Input
+#[must_use]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BitSpan {
+    span: Span,
+    offset: u8,
+    len: usize,
+}
+
+impl BitSpan {
+    pub(crate) fn new(span: Span, offset: u8, len: usize) -> Self {
+        debug_assert!(offset < 8);
+        Self { span, offset, len }
+    }
+
+    /// Returns the [`Span`] of bytes the bits were read from.
+    #[inline(always)]
+    pub fn span(self) -> Span {
+        self.span
+    }
+
+    /// Returns the offset of the first bit within the first byte spanned.
+    ///
+    /// This value is always less than `8`.
+    #[must_use]
+    #[inline(always)]
+    pub fn bit_offset(self) -> u8 {
+        self.offset
+    }
+
+    /// Returns the number of bits spanned.
+    #[must_use]
+    #[inline(always)]
+    pub fn bit_len(self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Debug for BitSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitSpan")
+            .field("span", &self.span)
+            .field("bit_offset", &self.offset)
+            .field("bit_len", &self.len)
+            .finish()
+    }
+}
@@ -3,6 +3,7 @@
 /// Used for retry functionality if enabled.
 #[must_use]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bound {
     /// Both sides of the [`Input`](crate::Input) may change in further passes.
     None,
@@ -0,0 +1,99 @@
+use crate::error::{
+    CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid, Length,
+};
+use crate::input::{Input, Private};
+
+use super::Bytes;
+
+impl<'i> Bytes<'i> {
+    /// Splits a LEB128 encoded integer of `bits` width from the input.
+    ///
+    /// The integer is returned as its raw 64 bits, sign extended if `signed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if the encoding was cut short and
+    /// [`ExpectedValid`] if the encoding was not canonical or did not fit
+    /// within `bits`.
+    pub(crate) fn split_leb128_for<E>(
+        self,
+        bits: usize,
+        signed: bool,
+        operation: CoreOperation,
+    ) -> Result<(u64, Bytes<'i>), E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        debug_assert!(bits > 0 && bits <= 64);
+        let bytes = self.as_dangerous();
+        let max_len = (bits + 6) / 7;
+        let mut value = 0_u64;
+        for (i, &byte) in bytes.iter().enumerate().take(max_len) {
+            let shift = 7 * i;
+            let group = u64::from(byte & 0x7f);
+            let more = byte & 0x80 != 0;
+            if i + 1 == max_len {
+                // The last byte possible may only hold the bits remaining for
+                // the width, with any unused bits matching the sign.
+                let unused = group >> (bits - shift - 1);
+                let fits = if signed {
+                    unused == 0 || unused == 0x7f >> (bits - shift - 1)
+                } else {
+                    unused >> 1 == 0
+                };
+                if more || !fits {
+                    return Err(self.leb128_invalid(i, operation, "LEB128 encoding within range"));
+                }
+            }
+            value |= group << shift;
+            if !more {
+                // The last byte must carry information, else a shorter
+                // encoding was possible.
+                let overlong = i > 0 && {
+                    let prev_sign = bytes[i - 1] & 0x40 != 0;
+                    if signed {
+                        (byte == 0x00 && !prev_sign) || (byte == 0x7f && prev_sign)
+                    } else {
+                        byte == 0x00
+                    }
+                };
+                if overlong {
+                    return Err(self.leb128_invalid(i, operation, "canonical LEB128 encoding"));
+                }
+                if signed && shift + 7 < 64 && byte & 0x40 != 0 {
+                    value |= !0 << (shift + 7);
+                }
+                // SAFETY: `i` is an index within the input, so `i + 1` is at
+                // most its length.
+                let (_, tail) = unsafe { self.split_at_byte_unchecked(i + 1) };
+                return Ok((value, tail));
+            }
+        }
+        Err(E::from(ExpectedLength {
+            len: Length::AtLeast(bytes.len() + 1),
+            found: None,
+            context: CoreContext {
+                span: self.span(),
+                operation,
+                expected: CoreExpected::EnoughInputFor("LEB128 encoding"),
+            },
+            input: self.into_maybe_string(),
+        }))
+    }
+
+    fn leb128_invalid<E>(self, last: usize, operation: CoreOperation, expected: &'static str) -> E
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        E::from(ExpectedValid {
+            retry_requirement: None,
+            context: CoreContext {
+                span: self.as_dangerous()[..=last].into(),
+                operation,
+                expected: CoreExpected::Valid(expected),
+            },
+            input: self.into_maybe_string(),
+        })
+    }
+}
@@ -1,6 +1,8 @@
 mod array;
+mod leb128;
 mod pattern;
 mod prefix;
+mod suffix;
 
 use core::slice::Iter as SliceIter;
 use core::{iter, str};
@@ -79,7 +81,7 @@ impl<'i> Bytes<'i> {
         E: From<ExpectedValid<'i>>,
         E: From<ExpectedLength<'i>>,
     {
-        str::from_utf8(self.as_dangerous()).map_err(|err| {
+        fast::from_utf8(self.as_dangerous()).map_err(|err| {
             self.clone().map_utf8_error(
                 err.error_len(),
                 err.valid_up_to(),
@@ -282,6 +284,7 @@ impl<'i> Bytes<'i> {
                 let first_invalid = unsafe { slice::first_unchecked(invalid) };
                 E::from(ExpectedLength {
                     len: Length::AtLeast(utf8::char_len(first_invalid)),
+                    found: None,
                     context: CoreContext {
                         span: invalid.into(),
                         operation,
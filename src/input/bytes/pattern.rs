@@ -1,4 +1,6 @@
-use crate::input::{Bytes, Pattern};
+#[cfg(feature = "alloc")]
+use crate::input::Needles;
+use crate::input::{ByteSet, Bytes, Pattern};
 use crate::util::fast;
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -17,6 +19,15 @@ where
             .map(|i| (i, 1))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        input
+            .as_dangerous()
+            .iter()
+            .copied()
+            .rposition(self)
+            .map(|i| (i, 1))
+    }
+
     fn find_reject(mut self, input: &Bytes<'i>) -> Option<usize> {
         input
             .as_dangerous()
@@ -35,6 +46,10 @@ unsafe impl<'i> Pattern<Bytes<'i>> for u8 {
         fast::find_u8_match(self, input.as_dangerous()).map(|index| (index, 1))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_u8_match_rev(self, input.as_dangerous()).map(|index| (index, 1))
+    }
+
     fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
         fast::find_u8_reject(self, input.as_dangerous())
     }
@@ -48,6 +63,10 @@ unsafe impl<'i> Pattern<Bytes<'i>> for &[u8] {
         fast::find_slice_match(self, input.as_dangerous()).map(|index| (index, self.len()))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_slice_match_rev(self, input.as_dangerous()).map(|index| (index, self.len()))
+    }
+
     fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
         fast::find_slice_reject(self, input.as_dangerous())
     }
@@ -58,6 +77,10 @@ unsafe impl<'i, const N: usize> Pattern<Bytes<'i>> for &[u8; N] {
         fast::find_slice_match(self, input.as_dangerous()).map(|index| (index, self.len()))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_slice_match_rev(self, input.as_dangerous()).map(|index| (index, self.len()))
+    }
+
     fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
         fast::find_slice_reject(self, input.as_dangerous())
     }
@@ -69,11 +92,48 @@ unsafe impl<'i> Pattern<Bytes<'i>> for &str {
             .map(|index| (index, self.len()))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_slice_match_rev(self.as_bytes(), input.as_dangerous())
+            .map(|index| (index, self.len()))
+    }
+
     fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
         fast::find_slice_reject(self.as_bytes(), input.as_dangerous())
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Set pattern
+
+unsafe impl<'i> Pattern<Bytes<'i>> for ByteSet<'_> {
+    fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_set_match(self, input.as_dangerous()).map(|index| (index, 1))
+    }
+
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        fast::find_set_match_rev(self, input.as_dangerous()).map(|index| (index, 1))
+    }
+
+    fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+        fast::find_set_reject(self, input.as_dangerous())
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<'i> Pattern<Bytes<'i>> for &Needles {
+    fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        Needles::find_match(self, input.as_dangerous(), false)
+    }
+
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        Needles::find_match_rev(self, input.as_dangerous(), false)
+    }
+
+    fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+        Needles::find_reject(self, input.as_dangerous(), false)
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Regex pattern
 
@@ -84,6 +144,19 @@ unsafe impl<'i> Pattern<Bytes<'i>> for &regex::bytes::Regex {
             .map(|m| (m.start(), m.end() - m.start()))
     }
 
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        // The rightmost match may overlap the last non-overlapping match, so
+        // search backwards from its start for the last index a match starts.
+        let haystack = input.as_dangerous();
+        let last = regex::bytes::Regex::find_iter(self, haystack).last()?;
+        (last.start()..=haystack.len())
+            .rev()
+            .find_map(|i| {
+                regex::bytes::Regex::find_at(self, haystack, i).filter(|m| m.start() == i)
+            })
+            .map(|m| (m.start(), m.end() - m.start()))
+    }
+
     fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
         let mut maybe_reject = 0;
         loop {
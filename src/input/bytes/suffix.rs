@@ -0,0 +1,40 @@
+use core::slice;
+
+use crate::input::{Bytes, Suffix};
+use crate::util::utf8::CharBytes;
+
+unsafe impl<'i> Suffix<Bytes<'i>> for u8 {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        input.as_dangerous().ends_with(slice::from_ref(self))
+    }
+}
+
+unsafe impl<'i> Suffix<Bytes<'i>> for char {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        let bytes = CharBytes::from(*self);
+        input.as_dangerous().ends_with(bytes.as_bytes())
+    }
+}
+
+unsafe impl<'i> Suffix<Bytes<'i>> for [u8] {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        input.as_dangerous().ends_with(self)
+    }
+}
+
+unsafe impl<'i> Suffix<Bytes<'i>> for str {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        input.as_dangerous().ends_with(self.as_bytes())
+    }
+}
+
+unsafe impl<'i, const N: usize> Suffix<Bytes<'i>> for [u8; N] {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        input.as_dangerous().ends_with(&self[..])
+    }
+}
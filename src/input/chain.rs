@@ -0,0 +1,419 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::{
+    CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValue, Length, WithContext,
+};
+use crate::fmt;
+use crate::reader::ChainReader;
+
+use super::{Bound, Bytes, Input};
+
+/// Input spanning multiple non-contiguous byte slices.
+///
+/// Segments could be the two halves of a ring buffer or a list of pooled
+/// buffers a frame was received into. A [`ChainReader`] reads across the
+/// segment boundaries without first copying the segments into a single
+/// buffer.
+///
+/// Errors are reported against the segment they occurred in, while retry
+/// requirements and the length of trailing input take into account all of
+/// the remaining segments.
+///
+/// A [`ChainReader`] supports a subset of the operations of a [`Reader`],
+/// see its documentation for what is supported.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::Invalid;
+/// use dangerous::input::Chain;
+///
+/// let segments: [&[u8]; 2] = [b"\x00", b"\x02hi"];
+/// let result: Result<_, Invalid> = Chain::new(&segments).read_all(|r| {
+///     let len = r.read_u16_be()?;
+///     r.skip(usize::from(len))
+/// });
+///
+/// assert!(result.is_ok());
+/// ```
+///
+/// [`Reader`]: crate::Reader
+#[derive(Clone)]
+#[must_use = "input must be consumed"]
+pub struct Chain<'i> {
+    /// The remaining input of the current segment.
+    head: Bytes<'i>,
+    /// The segments after the current segment.
+    tail: &'i [&'i [u8]],
+    bound: Bound,
+}
+
+impl<'i> Chain<'i> {
+    /// Creates a new `Chain` from a list of segments.
+    ///
+    /// Like [`dangerous::input()`], the start of the input is bound and the
+    /// end is not.
+    ///
+    /// [`dangerous::input()`]: crate::input()
+    pub fn new(segments: &'i [&'i [u8]]) -> Self {
+        let bound = Bound::Start;
+        let (head, tail) = match segments.split_first() {
+            Some((head, tail)) => (*head, tail),
+            None => (&[][..], segments),
+        };
+        let mut this = Self {
+            head: Bytes::new(head, bound),
+            tail,
+            bound,
+        };
+        this.skip_empty();
+        this
+    }
+
+    /// Returns the total length of the remaining segments.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail
+            .iter()
+            .fold(self.head.len(), |len, segment| len + segment.len())
+    }
+
+    /// Returns `true` if there is no input left in any segment.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        // Empty segments are always skipped.
+        self.head.is_empty()
+    }
+
+    /// Returns `true` if the remaining input is within a single segment.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_contiguous(&self) -> bool {
+        self.tail.is_empty()
+    }
+
+    /// Returns the remaining input as [`Bytes`] if it is within a single
+    /// segment.
+    #[must_use]
+    pub fn to_contiguous(&self) -> Option<Bytes<'i>> {
+        if self.is_contiguous() {
+            Some(self.head.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`Bound`] of the chain.
+    #[inline(always)]
+    pub fn bound(&self) -> Bound {
+        self.bound
+    }
+
+    /// Force the end of the chain to be bound.
+    pub fn into_bound(mut self) -> Self {
+        self.bound = Bound::force_close();
+        self.head = Bytes::new(self.head.as_dangerous(), self.bound);
+        self
+    }
+
+    /// Create a reader with the expectation all of the input is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the provided function does, or there is
+    /// trailing input.
+    pub fn read_all<F, T, E>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut ChainReader<'i, E>) -> Result<T, E>,
+        E: WithContext<'i>,
+        E: From<ExpectedLength<'i>>,
+    {
+        let mut r = ChainReader::new(self.clone());
+        match r.context(
+            CoreContext::from_operation(CoreOperation::ReadAll, self.head.span()),
+            f,
+        ) {
+            Ok(ok) if r.at_end() => Ok(ok),
+            Ok(_) => {
+                // The trailing input is reported against the current segment,
+                // with the length found across all of the remaining segments.
+                let remaining = r.into_remaining();
+                let found = remaining.len();
+                let remaining = remaining.head;
+                Err(E::from(ExpectedLength {
+                    len: Length::Exactly(0),
+                    found: Some(found),
+                    context: CoreContext {
+                        span: remaining.span(),
+                        operation: CoreOperation::ReadAll,
+                        expected: CoreExpected::NoTrailingInput,
+                    },
+                    input: remaining.into_maybe_string(),
+                }))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create a reader to read a part of the input and return the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provided function does.
+    pub fn read_partial<F, T, E>(self, f: F) -> Result<(T, Self), E>
+    where
+        F: FnOnce(&mut ChainReader<'i, E>) -> Result<T, E>,
+        E: WithContext<'i>,
+    {
+        let mut r = ChainReader::new(self.clone());
+        match r.context(
+            CoreContext::from_operation(CoreOperation::ReadPartial, self.head.span()),
+            f,
+        ) {
+            Ok(ok) => Ok((ok, r.into_remaining())),
+            Err(err) => Err(err),
+        }
+    }
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Private
+
+    /// Returns the remaining input of the current segment.
+    #[inline(always)]
+    pub(crate) fn head(&self) -> &Bytes<'i> {
+        &self.head
+    }
+
+    /// Moves past any empty segments so the head is only empty at the end.
+    fn skip_empty(&mut self) {
+        while self.head.is_empty() {
+            match self.tail.split_first() {
+                Some((head, tail)) => {
+                    self.head = Bytes::new(head, self.bound);
+                    self.tail = tail;
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Advances the chain by `len` bytes within the current segment.
+    fn advance_head(&mut self, len: usize) {
+        debug_assert!(len <= self.head.len());
+        self.head = Bytes::new(&self.head.as_dangerous()[len..], self.bound);
+        self.skip_empty();
+    }
+
+    /// Skips `len` bytes, returning `None` if there is not enough input.
+    pub(crate) fn split_at_opt(mut self, mut len: usize) -> Option<Self> {
+        while len > self.head.len() {
+            if self.tail.is_empty() {
+                return None;
+            }
+            len -= self.head.len();
+            self.head = Bytes::new(&[], self.bound);
+            self.skip_empty();
+        }
+        self.advance_head(len);
+        Some(self)
+    }
+
+    /// Copies the first `buf.len()` bytes into `buf`, returning the input
+    /// after them or `None` if there is not enough input.
+    pub(crate) fn split_into_opt(mut self, buf: &mut [u8]) -> Option<Self> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            if self.head.is_empty() {
+                return None;
+            }
+            let head = self.head.as_dangerous();
+            let len = head.len().min(buf.len() - filled);
+            buf[filled..filled + len].copy_from_slice(&head[..len]);
+            filled += len;
+            self.advance_head(len);
+        }
+        Some(self)
+    }
+
+    /// Splits a contiguous length of input from the current segment if it
+    /// is long enough.
+    #[cfg(feature = "alloc")]
+    fn split_head_opt(mut self, len: usize) -> Option<(Bytes<'i>, Self)> {
+        let head = self.head.as_dangerous();
+        if len > head.len() {
+            return None;
+        }
+        let taken = Bytes::new(&head[..len], Bound::force_close());
+        self.advance_head(len);
+        Some((taken, self))
+    }
+
+    /// Splits a length of input, borrowed if it is within the current segment
+    /// and copied otherwise.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn split_chunk_opt(self, len: usize) -> Option<(Chunk<'i>, Self)> {
+        if let Some((bytes, next)) = self.clone().split_head_opt(len) {
+            return Some((Chunk::Borrowed(bytes), next));
+        }
+        if len > self.len() {
+            return None;
+        }
+        let mut buf = alloc::vec![0; len];
+        self.split_into_opt(&mut buf)
+            .map(|next| (Chunk::Owned(buf), next))
+    }
+
+    /// Splits a prefix from the chain.
+    pub(crate) fn split_prefix<E>(
+        self,
+        prefix: &'i [u8],
+        operation: CoreOperation,
+    ) -> Result<Self, E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        let mut next = self.clone();
+        let mut matched = 0;
+        while matched < prefix.len() {
+            if next.head.is_empty() {
+                return Err(self.expected_length(prefix.len(), operation, "exact value"));
+            }
+            let head = next.head.as_dangerous();
+            let len = head.len().min(prefix.len() - matched);
+            let expected = &prefix[matched..];
+            if head[..len] != expected[..len] {
+                return Err(E::from(ExpectedValue {
+                    expected: expected.into(),
+                    context: CoreContext {
+                        span: (&head[..len]).into(),
+                        operation,
+                        expected: CoreExpected::ExactValue,
+                    },
+                    input: next.head.into_maybe_string(),
+                }));
+            }
+            matched += len;
+            next.advance_head(len);
+        }
+        Ok(next)
+    }
+
+    /// Returns an error for when `len` bytes were required but not all were
+    /// available.
+    ///
+    /// The error is reported against the last segment, with the length
+    /// expected adjusted so the retry requirement is correct for the whole
+    /// chain.
+    pub(crate) fn expected_length<E>(
+        &self,
+        len: usize,
+        operation: CoreOperation,
+        expected: &'static str,
+    ) -> E
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        let last = match self.tail.last() {
+            Some(last) => Bytes::new(last, self.bound),
+            None => self.head.clone(),
+        };
+        let missing = len.saturating_sub(self.len());
+        E::from(ExpectedLength {
+            len: Length::AtLeast(last.len() + missing),
+            found: None,
+            context: CoreContext {
+                span: last.span(),
+                operation,
+                expected: CoreExpected::EnoughInputFor(expected),
+            },
+            input: last.into_maybe_string(),
+        })
+    }
+}
+
+impl<'i> fmt::Debug for Chain<'i> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chain")
+            .field("head", &self.head)
+            .field("tail", &self.tail)
+            .field("bound", &self.bound)
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Chunk
+
+/// A length of input taken from a [`Chain`].
+///
+/// Input within a single segment is borrowed, while input straddling
+/// segments is copied into a contiguous buffer.
+#[derive(Clone)]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub enum Chunk<'i> {
+    /// Input borrowed from a single segment.
+    Borrowed(Bytes<'i>),
+    /// Input copied from multiple segments.
+    Owned(Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'i> Chunk<'i> {
+    /// Returns the length of the chunk.
+    #[must_use]
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.as_dangerous().len()
+    }
+
+    /// Returns `true` if the chunk is empty.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.as_dangerous().is_empty()
+    }
+
+    /// Returns `true` if the chunk is borrowed from a single segment.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed(_))
+    }
+
+    /// Returns the underlying byte slice.
+    ///
+    /// See [`Bytes::as_dangerous`] for naming.
+    #[must_use]
+    pub fn as_dangerous(&self) -> &[u8] {
+        match self {
+            Self::Borrowed(bytes) => bytes.as_dangerous(),
+            Self::Owned(vec) => vec,
+        }
+    }
+
+    /// Returns the chunk as [`Bytes`] that can be read, borrowing from the
+    /// chunk if it was copied.
+    pub fn to_bytes(&self) -> Bytes<'_> {
+        match self {
+            Self::Borrowed(bytes) => bytes.clone(),
+            Self::Owned(vec) => Bytes::new(vec, Bound::force_close()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'i> fmt::Debug for Chunk<'i> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Borrowed(bytes) => f.debug_tuple("Borrowed").field(bytes).finish(),
+            Self::Owned(vec) => f
+                .debug_tuple("Owned")
+                .field(&Bytes::new(vec, Bound::force_close()))
+                .finish(),
+        }
+    }
+}
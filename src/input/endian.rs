@@ -0,0 +1,25 @@
+use crate::fmt;
+
+/// Byte order used when reading a multi-byte number from [`Bytes`].
+///
+/// [`Bytes`]: crate::Bytes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Endian {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+    /// The byte order of the target platform.
+    Native,
+}
+
+impl fmt::DisplayBase for Endian {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        match *self {
+            Self::Little => w.write_str("little endian"),
+            Self::Big => w.write_str("big endian"),
+            Self::Native => w.write_str("native endian"),
+        }
+    }
+}
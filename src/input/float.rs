@@ -0,0 +1,81 @@
+use core::num::FpCategory;
+
+/// Policy for which classes of floating point values are accepted when read
+/// from [`Bytes`].
+///
+/// By default every value is accepted. Classes can be rejected by chaining the
+/// `reject_*` functions.
+///
+/// ```
+/// use dangerous::input::FloatPolicy;
+///
+/// let policy = FloatPolicy::any().reject_nan().reject_infinite();
+///
+/// assert_eq!(policy, FloatPolicy::finite());
+/// ```
+///
+/// [`Bytes`]: crate::Bytes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[must_use]
+pub struct FloatPolicy {
+    nan: bool,
+    infinite: bool,
+    subnormal: bool,
+}
+
+impl FloatPolicy {
+    /// Accept any floating point value.
+    pub const fn any() -> Self {
+        Self {
+            nan: true,
+            infinite: true,
+            subnormal: true,
+        }
+    }
+
+    /// Accept only finite floating point values.
+    pub const fn finite() -> Self {
+        Self::any().reject_nan().reject_infinite()
+    }
+
+    /// Accept only zero or normal floating point values.
+    pub const fn normal() -> Self {
+        Self::finite().reject_subnormal()
+    }
+
+    /// Reject NaN values, both quiet and signalling.
+    pub const fn reject_nan(mut self) -> Self {
+        self.nan = false;
+        self
+    }
+
+    /// Reject positive and negative infinity.
+    pub const fn reject_infinite(mut self) -> Self {
+        self.infinite = false;
+        self
+    }
+
+    /// Reject subnormal values.
+    pub const fn reject_subnormal(mut self) -> Self {
+        self.subnormal = false;
+        self
+    }
+
+    /// Checks a floating point category against the policy.
+    ///
+    /// Returns a description of what was expected if it was rejected.
+    pub(crate) fn check(self, category: FpCategory) -> Result<(), &'static str> {
+        match category {
+            FpCategory::Nan if !self.nan => Err("number that is not NaN"),
+            FpCategory::Infinite if !self.infinite => Err("finite number"),
+            FpCategory::Subnormal if !self.subnormal => Err("number that is not subnormal"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for FloatPolicy {
+    fn default() -> Self {
+        Self::any()
+    }
+}
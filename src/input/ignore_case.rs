@@ -0,0 +1,178 @@
+use crate::util::fast;
+
+use super::{ByteLength, Bytes, Pattern, Prefix, String, Suffix};
+
+/// Wraps a value to be matched ignoring ASCII case.
+///
+/// Can be used as a [`Prefix`] (for example with [`Reader::consume()`]), a
+/// [`Suffix`] or as a [`Pattern`] (for example with [`Reader::take_until()`])
+/// within either `Bytes` or `String` input. Only ASCII letters are compared case
+/// insensitively, all other bytes must match exactly.
+///
+/// Errors show the value as it was provided.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Input, Invalid};
+/// use dangerous::input::IgnoreAsciiCase;
+///
+/// let input = dangerous::input(b"CONTENT-TYPE: text/html; Charset=utf-8");
+/// let (media_type, rest) = input
+///     .read_partial::<_, _, Invalid>(|r| {
+///         r.consume(IgnoreAsciiCase::new(b"Content-Type: "))?;
+///         r.take_until(IgnoreAsciiCase::new(b"; charset="))
+///     })
+///     .unwrap();
+///
+/// assert_eq!(media_type, b"text/html"[..]);
+/// assert_eq!(rest, b"; Charset=utf-8"[..]);
+/// ```
+///
+/// [`Reader::consume()`]: crate::Reader::consume()
+/// [`Reader::take_until()`]: crate::Reader::take_until()
+#[derive(Debug, Copy, Clone)]
+#[must_use]
+pub struct IgnoreAsciiCase<T>(T);
+
+impl<T> IgnoreAsciiCase<T> {
+    /// Wraps a value to be matched ignoring ASCII case.
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the wrapped value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> IgnoreAsciiCase<T>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+
+    #[inline(always)]
+    fn is_prefix_of_bytes(&self, input: &[u8]) -> bool {
+        let needle = self.as_bytes();
+        input.len() >= needle.len() && input[..needle.len()].eq_ignore_ascii_case(needle)
+    }
+
+    #[inline(always)]
+    fn is_suffix_of_bytes(&self, input: &[u8]) -> bool {
+        let needle = self.as_bytes();
+        input.len() >= needle.len()
+            && input[input.len() - needle.len()..].eq_ignore_ascii_case(needle)
+    }
+}
+
+unsafe impl<T> ByteLength for IgnoreAsciiCase<T>
+where
+    T: AsRef<[u8]>,
+{
+    /// Returns the length of the wrapped value's bytes.
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Prefix
+//
+// As only ASCII bytes may differ (and only by case), a UTF-8 value matches
+// the same char boundaries within a `String` as an exact match would.
+
+unsafe impl<'i, T> Prefix<Bytes<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline(always)]
+    fn is_prefix_of(&self, input: &Bytes<'i>) -> bool {
+        self.is_prefix_of_bytes(input.as_dangerous())
+    }
+}
+
+unsafe impl<'i, T> Prefix<String<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<str> + AsRef<[u8]>,
+{
+    #[inline(always)]
+    fn is_prefix_of(&self, input: &String<'i>) -> bool {
+        self.is_prefix_of_bytes(input.as_dangerous().as_bytes())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Suffix
+
+unsafe impl<'i, T> Suffix<Bytes<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &Bytes<'i>) -> bool {
+        self.is_suffix_of_bytes(input.as_dangerous())
+    }
+}
+
+unsafe impl<'i, T> Suffix<String<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<str> + AsRef<[u8]>,
+{
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &String<'i>) -> bool {
+        self.is_suffix_of_bytes(input.as_dangerous().as_bytes())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Pattern
+
+unsafe impl<'i, T> Pattern<Bytes<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<[u8]>,
+{
+    fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        fast::find_slice_match_ignore_ascii_case(needle, input.as_dangerous())
+            .map(|index| (index, needle.len()))
+    }
+
+    fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        fast::find_slice_match_rev_ignore_ascii_case(needle, input.as_dangerous())
+            .map(|index| (index, needle.len()))
+    }
+
+    fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+        fast::find_slice_reject_ignore_ascii_case(self.as_bytes(), input.as_dangerous())
+    }
+}
+
+unsafe impl<'i, T> Pattern<String<'i>> for IgnoreAsciiCase<T>
+where
+    T: AsRef<str> + AsRef<[u8]>,
+{
+    fn find_match(self, input: &String<'i>) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        fast::find_slice_match_ignore_ascii_case(needle, input.as_dangerous().as_bytes())
+            .map(|index| (index, needle.len()))
+    }
+
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        fast::find_slice_match_rev_ignore_ascii_case(needle, input.as_dangerous().as_bytes())
+            .map(|index| (index, needle.len()))
+    }
+
+    fn find_reject(self, input: &String<'i>) -> Option<usize> {
+        fast::find_slice_reject_ignore_ascii_case(self.as_bytes(), input.as_dangerous().as_bytes())
+    }
+}
@@ -0,0 +1,20 @@
+use super::Endian;
+
+/// Encoding of a length header read before a length of input.
+///
+/// Used with [`BytesReader::take_length_prefixed()`].
+///
+/// [`BytesReader::take_length_prefixed()`]: crate::BytesReader::take_length_prefixed()
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LengthPrefix {
+    /// A single byte length.
+    U8,
+    /// A two byte length.
+    U16(Endian),
+    /// A four byte length.
+    U32(Endian),
+    /// An eight byte length.
+    U64(Endian),
+    /// An unsigned LEB128 (varint) encoded length of at most 64 bits.
+    Uleb128,
+}
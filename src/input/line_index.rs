@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(feature = "unicode")]
+use unicode_width::UnicodeWidthChar;
+
+use crate::fmt;
+use crate::input::{Input, PrivateExt, Span};
+use crate::util::line;
+
+/// The unit columns are counted in by a [`LineIndex`].
+///
+/// Any invalid UTF-8 bytes are counted as a single unit regardless of the
+/// column unit chosen.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ColumnUnit {
+    /// Columns are counted in bytes.
+    Byte,
+    /// Columns are counted in unicode scalar values (`char`s).
+    Char,
+    /// Columns are counted in UTF-16 code units.
+    Utf16,
+    /// Columns are counted in the display width of each `char`.
+    ///
+    /// Without the `unicode` feature enabled this is the same as
+    /// [`ColumnUnit::Char`].
+    Width,
+}
+
+impl Default for ColumnUnit {
+    fn default() -> Self {
+        Self::Byte
+    }
+}
+
+/// A line and column position within input.
+///
+/// Both the line and column are one-based.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct LineColumn {
+    /// The line number.
+    pub line: usize,
+    /// The column number, in the [`ColumnUnit`] of the [`LineIndex`].
+    pub column: usize,
+}
+
+impl fmt::DisplayBase for LineColumn {
+    fn fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_usize(self.line)?;
+        w.write_char(':')?;
+        w.write_usize(self.column)
+    }
+}
+
+impl fmt::Display for LineColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::DisplayBase::fmt(self, f)
+    }
+}
+
+/// Resolves [`Span`]s within input to line and column positions.
+///
+/// The index is built once by scanning the input for newlines (`\n`), after
+/// which each resolution is a binary search over the line starts and a scan
+/// of the line the position is within.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::Input;
+/// use dangerous::input::{ColumnUnit, LineColumn, LineIndex};
+///
+/// let input = dangerous::input("hello\nwörld");
+/// let index = LineIndex::new(&input).column_unit(ColumnUnit::Char);
+/// let span = input.split_at_byte_opt(11).unwrap().0.span();
+///
+/// assert_eq!(index.line_count(), 2);
+/// assert_eq!(
+///     index.resolve(span.end()).unwrap().start,
+///     LineColumn { line: 2, column: 5 },
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct LineIndex<'i> {
+    bytes: &'i [u8],
+    line_starts: Vec<usize>,
+    column_unit: ColumnUnit,
+}
+
+impl<'i> LineIndex<'i> {
+    /// Create a new `LineIndex` given [`Input`].
+    ///
+    /// Columns are counted in bytes by default.
+    #[must_use]
+    pub fn new(input: &impl Input<'i>) -> Self {
+        let bytes = input.as_dangerous_bytes();
+        let mut line_starts = Vec::with_capacity(bytes.len() / 32 + 1);
+        line_starts.extend(line::lines(bytes).map(|line| line.start));
+        Self {
+            bytes,
+            line_starts,
+            column_unit: ColumnUnit::default(),
+        }
+    }
+
+    /// Set the unit columns are counted in.
+    #[must_use]
+    pub fn column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.column_unit = unit;
+        self
+    }
+
+    /// Returns the number of lines within the input.
+    ///
+    /// Input always has at least one line, even if empty.
+    #[must_use]
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the [`Span`] of a one-based line number, not including the
+    /// line ending (`\n` or `\r\n`).
+    ///
+    /// Returns `None` if the line does not exist.
+    #[must_use]
+    pub fn line_span(&self, line: usize) -> Option<Span> {
+        let range = self.line_range(line.checked_sub(1)?)?;
+        Some(Span::from(&self.bytes[range]))
+    }
+
+    /// Returns the [`LineColumn`] at a byte offset within the input.
+    ///
+    /// Returns `None` if the offset is greater than the input length.
+    #[must_use]
+    pub fn position(&self, offset: usize) -> Option<LineColumn> {
+        if offset > self.bytes.len() {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let before = &self.bytes[self.line_starts[line]..offset];
+        Some(LineColumn {
+            line: line + 1,
+            column: count_units(before, self.column_unit) + 1,
+        })
+    }
+
+    /// Returns the start and end [`LineColumn`] of a [`Span`].
+    ///
+    /// Returns `None` if the span is not within the input.
+    #[must_use]
+    pub fn resolve(&self, span: Span) -> Option<Range<LineColumn>> {
+        let range = span.range_of(Span::from(self.bytes))?;
+        Some(self.position(range.start)?..self.position(range.end)?)
+    }
+
+    fn line_range(&self, index: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(index)?;
+        let (end, _) = line::line_end(self.bytes, start);
+        Some(start..end)
+    }
+}
+
+impl<'i> fmt::Debug for LineIndex<'i> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LineIndex")
+            .field("line_count", &self.line_count())
+            .field("column_unit", &self.column_unit)
+            .finish()
+    }
+}
+
+fn count_units(bytes: &[u8], unit: ColumnUnit) -> usize {
+    if unit == ColumnUnit::Byte {
+        return bytes.len();
+    }
+    let mut count = 0;
+    line::for_each_char(bytes, |c| {
+        count += match unit {
+            ColumnUnit::Byte | ColumnUnit::Char => 1,
+            ColumnUnit::Utf16 => c.len_utf16(),
+            ColumnUnit::Width => char_width(c),
+        };
+    });
+    count
+}
+
+#[cfg(feature = "unicode")]
+#[inline]
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(1)
+}
+
+#[cfg(not(feature = "unicode"))]
+#[inline]
+fn char_width(_c: char) -> usize {
+    1
+}
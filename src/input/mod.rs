@@ -1,25 +1,57 @@
 //! Input support.
 
+mod bits;
 mod bound;
 mod byte_len;
 mod bytes;
+mod chain;
+mod endian;
 mod entry;
+mod float;
+mod ignore_case;
+mod length_prefix;
+#[cfg(feature = "alloc")]
+mod line_index;
 mod pattern;
 mod prefix;
+#[cfg(feature = "regex")]
+mod regex;
 mod span;
+mod split;
 mod string;
+mod suffix;
 mod token;
 mod traits;
 
+pub use self::bits::{BitOrder, BitSpan};
 pub use self::bound::Bound;
 pub use self::byte_len::ByteLength;
 pub use self::bytes::{ByteArray, Bytes};
-pub use self::pattern::Pattern;
+pub use self::chain::Chain;
+#[cfg(feature = "alloc")]
+pub use self::chain::Chunk;
+pub use self::endian::Endian;
+pub use self::float::FloatPolicy;
+pub use self::ignore_case::IgnoreAsciiCase;
+pub use self::length_prefix::LengthPrefix;
+#[cfg(feature = "alloc")]
+pub use self::line_index::{ColumnUnit, LineColumn, LineIndex};
+#[cfg(feature = "alloc")]
+pub use self::pattern::Needles;
+pub use self::pattern::{ByteSet, Pattern};
 pub use self::prefix::Prefix;
+#[cfg(all(feature = "regex", feature = "alloc"))]
+pub use self::regex::Captures;
 pub use self::span::Span;
+#[cfg(feature = "serde")]
+pub use self::span::{SpanIn, SpanSeed};
+pub use self::split::{Lines, Split, SplitN, SplitTerminator};
 pub use self::string::{MaybeString, String};
+pub use self::suffix::Suffix;
 pub use self::token::{Token, TokenType};
 pub use self::traits::Input;
 
 pub(crate) use self::entry::IntoInput;
+#[cfg(feature = "bytes")]
+pub(crate) use self::span::Parent;
 pub(crate) use self::traits::{Private, PrivateExt};
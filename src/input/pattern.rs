@@ -1,8 +1,20 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::Value;
+use crate::fmt;
+#[cfg(feature = "alloc")]
+use crate::input::{Bound, Bytes};
+
 /// Implemented for structures that can be found within an
 /// [`Input`](crate::Input).
 ///
 /// You can search for a `char` or `&str` within either `Bytes` or `String`, but
-/// only a `u8` and `&[u8]` within `Bytes`.
+/// only a `u8` and `&[u8]` within `Bytes`. A [`ByteSet`] or [`Needles`] can be
+/// searched for within either to match any of a set of bytes or needles.
+///
+/// A `FnMut(u8) -> bool` within `Bytes` or a `FnMut(char) -> bool` within
+/// `String` matches where the function returns `true`.
 ///
 /// Empty slices are invalid patterns and have the following behaviour:
 ///
@@ -23,7 +35,246 @@ pub unsafe trait Pattern<I> {
     /// there was no match.
     fn find_match(self, input: &I) -> Option<(usize, usize)>;
 
+    /// Returns the byte index and byte length of the last match and `None` if
+    /// there was no match.
+    ///
+    /// The last match is the one starting at the greatest index, which may
+    /// overlap an earlier match as with [`str::rfind`].
+    fn find_match_rev(self, input: &I) -> Option<(usize, usize)>;
+
     /// Returns the byte index of the first reject and `None` if there was no
     /// reject.
     fn find_reject(self, input: &I) -> Option<usize>;
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// ByteSet
+
+/// A set of bytes, any of which is matched as a [`Pattern`].
+///
+/// Membership is checked with a 256-bit table and the set can be built in a
+/// `const` context, so it can be reused across searches for free. With the
+/// `simd` feature enabled, finding a match of a set with up to three bytes is
+/// SIMD optimised.
+///
+/// Within `String` input only the ASCII bytes of the set are matched, so
+/// searches always stop on a char boundary.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Input, Invalid};
+/// use dangerous::input::ByteSet;
+///
+/// const STOP: ByteSet<'static> = ByteSet::new(b"\r\n;#");
+///
+/// let result: Result<_, Invalid> = dangerous::input(b"key=value;rest").read_partial(|r| {
+///     r.take_until(STOP)
+/// });
+///
+/// assert_eq!(result.unwrap().0, b"key=value"[..]);
+/// ```
+#[derive(Copy, Clone)]
+pub struct ByteSet<'a> {
+    bytes: &'a [u8],
+    table: [u64; 4],
+}
+
+impl<'a> ByteSet<'a> {
+    /// Creates a new `ByteSet` of the provided bytes.
+    #[must_use]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        let mut table = [0; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            let byte = bytes[i];
+            table[(byte >> 6) as usize] |= 1 << (byte & 63);
+            i += 1;
+        }
+        Self { bytes, table }
+    }
+
+    /// Returns `true` if the byte is within the set.
+    #[must_use]
+    #[inline(always)]
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.table[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+    }
+
+    /// Returns the bytes the set was created with.
+    #[must_use]
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` if the set only contains ASCII bytes.
+    #[inline(always)]
+    pub(crate) fn is_ascii(&self) -> bool {
+        self.table[2] == 0 && self.table[3] == 0
+    }
+}
+
+impl fmt::Debug for ByteSet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ByteSet").field(&Value::from(*self)).finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Needles
+
+/// A set of byte sequences, any of which is matched as a [`Pattern`].
+///
+/// When more than one needle matches at the same index, the needle provided
+/// first is matched. Empty needles never match.
+///
+/// With the `aho-corasick` feature enabled the needles are searched for with
+/// an Aho-Corasick automaton, using the SIMD accelerated Teddy algorithm where
+/// supported. Otherwise each needle is checked at each index in turn. The
+/// feature is not part of `simd` as it requires Rust 1.60.
+///
+/// Within `String` input, needles that are not valid UTF-8 never match.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::Input;
+/// use dangerous::input::Needles;
+///
+/// let keywords = Needles::new(&["</script>", "</style>"]);
+/// let input = dangerous::input(b"a { }</style>");
+/// let (body, _) = input
+///     .read_partial::<_, _, dangerous::Invalid>(|r| r.take_until(&keywords))
+///     .unwrap();
+///
+/// assert_eq!(body, b"a { }"[..]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Needles {
+    patterns: Vec<Vec<u8>>,
+    /// `true` if all of the needles are valid UTF-8.
+    utf8: bool,
+    #[cfg(feature = "aho-corasick")]
+    searcher: Option<aho_corasick::AhoCorasick>,
+}
+
+#[cfg(feature = "alloc")]
+impl Needles {
+    /// Creates a new `Needles` pattern from a list of needles.
+    pub fn new<I>(needles: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let needles: Vec<Vec<u8>> = needles
+            .into_iter()
+            .map(|needle| needle.as_ref().to_vec())
+            .filter(|needle| !needle.is_empty())
+            .collect();
+        let utf8 = needles
+            .iter()
+            .all(|needle| core::str::from_utf8(needle).is_ok());
+        #[cfg(feature = "aho-corasick")]
+        let searcher = aho_corasick::AhoCorasick::builder()
+            .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+            .build(&needles)
+            .ok();
+        Self {
+            patterns: needles,
+            utf8,
+            #[cfg(feature = "aho-corasick")]
+            searcher,
+        }
+    }
+
+    /// Returns the number of needles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns `true` if there are no needles.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns an iterator over the needles, in the order provided.
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.patterns.iter().map(Vec::as_slice)
+    }
+
+    /// Returns `true` if all of the needles are valid UTF-8.
+    pub(crate) fn is_utf8(&self) -> bool {
+        self.utf8
+    }
+
+    /// Returns the index and length of the first match.
+    ///
+    /// If `utf8` is `true`, needles that are not valid UTF-8 are skipped.
+    pub(crate) fn find_match(&self, haystack: &[u8], utf8: bool) -> Option<(usize, usize)> {
+        #[cfg(feature = "aho-corasick")]
+        if let (Some(searcher), true) = (&self.searcher, self.utf8 || !utf8) {
+            return searcher
+                .find(haystack)
+                .map(|m| (m.start(), m.end() - m.start()));
+        }
+        (0..haystack.len()).find_map(|i| {
+            self.matching(&haystack[i..], utf8)
+                .map(|needle| (i, needle.len()))
+        })
+    }
+
+    /// Returns the index and length of the last match.
+    ///
+    /// Of the needles matching at the same index, the first provided is
+    /// returned. If `utf8` is `true`, needles that are not valid UTF-8 are
+    /// skipped.
+    pub(crate) fn find_match_rev(&self, haystack: &[u8], utf8: bool) -> Option<(usize, usize)> {
+        (0..haystack.len()).rev().find_map(|i| {
+            self.matching(&haystack[i..], utf8)
+                .map(|needle| (i, needle.len()))
+        })
+    }
+
+    /// Returns the index of the first byte not within a run of needles.
+    ///
+    /// If `utf8` is `true`, needles that are not valid UTF-8 are skipped.
+    pub(crate) fn find_reject(&self, haystack: &[u8], utf8: bool) -> Option<usize> {
+        if self.patterns.is_empty() {
+            return Some(0);
+        }
+        let mut i = 0;
+        while i < haystack.len() {
+            match self.matching(&haystack[i..], utf8) {
+                Some(needle) => i += needle.len(),
+                None => return Some(i),
+            }
+        }
+        None
+    }
+
+    /// Returns the first needle the haystack starts with.
+    fn matching(&self, haystack: &[u8], utf8: bool) -> Option<&[u8]> {
+        self.patterns
+            .iter()
+            .map(Vec::as_slice)
+            .filter(|needle| !utf8 || self.utf8 || core::str::from_utf8(needle).is_ok())
+            .find(|needle| haystack.starts_with(needle))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for Needles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.patterns
+                    .iter()
+                    .map(|needle| Bytes::new(needle, Bound::StartEnd)),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::{CoreContext, CoreExpected, CoreOperation, ExpectedValid, RetryRequirement};
+#[cfg(feature = "alloc")]
+use crate::fmt;
+
+use super::{Bytes, Input, Private, String};
+
+///////////////////////////////////////////////////////////////////////////////
+// Captures
+
+/// Sub-inputs captured by the groups of a regex.
+///
+/// Created via [`BytesReader::captures()`] or [`StringReader::captures()`].
+///
+/// Each captured group is a sub-input of the input read, with the span of
+/// where it was found. Groups that did not participate in the match are
+/// `None`.
+///
+/// [`BytesReader::captures()`]: crate::BytesReader::captures()
+/// [`StringReader::captures()`]: crate::StringReader::captures()
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "regex", feature = "alloc"))))]
+pub struct Captures<'r, I> {
+    names: Vec<Option<&'r str>>,
+    groups: Vec<Option<I>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'i, I> Captures<'_, I>
+where
+    I: Input<'i>,
+{
+    /// Returns the input captured by the group at `index` if it matched.
+    ///
+    /// The group at index `0` is always the whole match.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<I> {
+        self.groups.get(index).cloned().flatten()
+    }
+
+    /// Returns the input captured by the group named `name` if it matched.
+    #[must_use]
+    pub fn name(&self, name: &str) -> Option<I> {
+        self.names
+            .iter()
+            .position(|group| *group == Some(name))
+            .and_then(|index| self.get(index))
+    }
+
+    /// Returns an iterator over all of the groups, in the order they appear
+    /// within the regex.
+    pub fn iter(&self) -> impl Iterator<Item = Option<I>> + '_ {
+        self.groups.iter().cloned()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'i, I> fmt::Debug for Captures<'_, I>
+where
+    I: Input<'i>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Captures")
+            .field("names", &self.names)
+            .field("groups", &self.groups)
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Splitting
+
+impl<'i> Bytes<'i> {
+    /// Splits the input at the end of a regex match at the start of the
+    /// input.
+    pub(crate) fn split_regex_for<E>(
+        self,
+        regex: &regex::bytes::Regex,
+        operation: CoreOperation,
+    ) -> Result<(Bytes<'i>, Bytes<'i>), E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        if !is_anchored(regex.as_str()) {
+            return Err(expected_anchored(self, operation));
+        }
+        match regex.find(self.as_dangerous()) {
+            // SAFETY: the regex guarantees the match end is within the input.
+            Some(m) if m.start() == 0 => Ok(unsafe { self.split_at_byte_unchecked(m.end()) }),
+            _ => Err(expected_match(self, operation)),
+        }
+    }
+
+    /// Splits the input at the end of a regex match at the start of the
+    /// input, returning the captured groups.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn split_captures_for<'r, E>(
+        self,
+        regex: &'r regex::bytes::Regex,
+        operation: CoreOperation,
+    ) -> Result<(Captures<'r, Bytes<'i>>, Bytes<'i>), E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        if !is_anchored(regex.as_str()) {
+            return Err(expected_anchored(self, operation));
+        }
+        let bytes = self.as_dangerous();
+        match regex.captures(bytes) {
+            Some(captures) if captures.get(0).map_or(false, |m| m.start() == 0) => {
+                let end = captures.get(0).map_or(0, |m| m.end());
+                let bound = self.bound().close_end();
+                let groups = captures
+                    .iter()
+                    .map(|group| group.map(|m| Bytes::new(&bytes[m.range()], bound)))
+                    .collect();
+                let captures = Captures {
+                    names: regex.capture_names().collect(),
+                    groups,
+                };
+                // SAFETY: the regex guarantees the match end is within the
+                // input.
+                let (_, tail) = unsafe { self.split_at_byte_unchecked(end) };
+                Ok((captures, tail))
+            }
+            _ => Err(expected_match(self, operation)),
+        }
+    }
+}
+
+impl<'i> String<'i> {
+    /// Splits the input at the end of a regex match at the start of the
+    /// input.
+    pub(crate) fn split_regex_for<E>(
+        self,
+        regex: &regex::Regex,
+        operation: CoreOperation,
+    ) -> Result<(String<'i>, String<'i>), E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        if !is_anchored(regex.as_str()) {
+            return Err(expected_anchored(self, operation));
+        }
+        match regex.find(self.as_dangerous()) {
+            // SAFETY: the regex guarantees the match end is a char boundary
+            // within the input.
+            Some(m) if m.start() == 0 => Ok(unsafe { self.split_at_byte_unchecked(m.end()) }),
+            _ => Err(expected_match(self, operation)),
+        }
+    }
+
+    /// Splits the input at the end of a regex match at the start of the
+    /// input, returning the captured groups.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn split_captures_for<'r, E>(
+        self,
+        regex: &'r regex::Regex,
+        operation: CoreOperation,
+    ) -> Result<(Captures<'r, String<'i>>, String<'i>), E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        if !is_anchored(regex.as_str()) {
+            return Err(expected_anchored(self, operation));
+        }
+        let string = self.as_dangerous();
+        match regex.captures(string) {
+            Some(captures) if captures.get(0).map_or(false, |m| m.start() == 0) => {
+                let end = captures.get(0).map_or(0, |m| m.end());
+                let bound = self.bound().close_end();
+                let groups = captures
+                    .iter()
+                    .map(|group| group.map(|m| String::new(m.as_str(), bound)))
+                    .collect();
+                let captures = Captures {
+                    names: regex.capture_names().collect(),
+                    groups,
+                };
+                // SAFETY: the regex guarantees the match end is a char
+                // boundary within the input.
+                let (_, tail) = unsafe { self.split_at_byte_unchecked(end) };
+                Ok((captures, tail))
+            }
+            _ => Err(expected_match(self, operation)),
+        }
+    }
+}
+
+/// Returns `true` if the regex pattern starts with an anchor to the start of
+/// the input.
+///
+/// Searching with an anchored regex stops at the first position that can't
+/// match, instead of searching the rest of the input for a match that would
+/// be rejected anyway for not being at the start.
+fn is_anchored(pattern: &str) -> bool {
+    pattern.starts_with('^') || pattern.starts_with("\\A")
+}
+
+/// Returns an error for when a regex is not anchored to the start of the
+/// input, which can't be fixed by more input.
+fn expected_anchored<'i, I, E>(input: I, operation: CoreOperation) -> E
+where
+    I: Input<'i>,
+    E: From<ExpectedValid<'i>>,
+{
+    E::from(ExpectedValid {
+        retry_requirement: None,
+        context: CoreContext {
+            span: input.span(),
+            operation,
+            expected: CoreExpected::Valid("regex anchored with `^` or `\\A`"),
+        },
+        input: input.into_maybe_string(),
+    })
+}
+
+/// Returns an error for when a regex did not match at the start of the input.
+///
+/// As the regex may match once more input is available, more input is
+/// requested if the input is not bound.
+fn expected_match<'i, I, E>(input: I, operation: CoreOperation) -> E
+where
+    I: Input<'i>,
+    E: From<ExpectedValid<'i>>,
+{
+    let retry_requirement = if input.is_bound() {
+        None
+    } else {
+        RetryRequirement::new(1)
+    };
+    E::from(ExpectedValid {
+        retry_requirement,
+        context: CoreContext {
+            span: input.span(),
+            operation,
+            expected: CoreExpected::PatternMatch,
+        },
+        input: input.into_maybe_string(),
+    })
+}
@@ -176,7 +176,9 @@ impl Span {
     /// if `self` is not within the parent or does not align with start and end
     /// token boundaries.
     ///
-    /// You can get a span of [`Input`], `&str` or `&[u8]`.
+    /// You can get a span of [`Input`], `&str` or `&[u8]`. With the `bytes`
+    /// feature enabled you can also get a span of a `bytes::Bytes`, returning
+    /// a cheap reference counted slice of it.
     ///
     /// # Example
     ///
@@ -248,6 +250,71 @@ impl Span {
         }
     }
 
+    /// Pairs the span with a parent for serializing as `start` and `end`
+    /// offsets within the parent.
+    ///
+    /// Serializing fails if `self` is not within the parent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Span;
+    ///
+    /// let parent = &b"hello world"[..];
+    /// let span = Span::from(&parent[6..]);
+    /// let json = serde_json::to_string(&span.serialize_in(parent.into())).unwrap();
+    ///
+    /// assert_eq!(json, r#"{"start":6,"end":11}"#);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[inline(always)]
+    pub fn serialize_in(self, parent: Span) -> SpanIn {
+        SpanIn { span: self, parent }
+    }
+
+    /// Returns a seed for deserializing a span from `start` and `end` offsets
+    /// within a parent.
+    ///
+    /// Deserializing fails if the offsets are not within the parent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Span;
+    /// use serde::de::DeserializeSeed;
+    ///
+    /// let parent = &b"hello world"[..];
+    /// let mut de = serde_json::Deserializer::from_str(r#"{"start":6,"end":11}"#);
+    /// let span = Span::seed(parent.into()).deserialize(&mut de).unwrap();
+    ///
+    /// assert_eq!(span.of(parent), Some(&b"world"[..]));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[inline(always)]
+    pub fn seed(parent: Span) -> SpanSeed {
+        SpanSeed { parent }
+    }
+
+    /// Returns the sub span of `self` given `start` and `end` offsets or `None`
+    /// if the offsets are not within `self`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn sub_span(self, range: Range<usize>) -> Option<Span> {
+        if range.start <= range.end && range.end <= self.len() {
+            // SAFETY: we have checked that both offsets are within the span, so
+            // the pointers are within the same allocation and non-null.
+            unsafe {
+                Some(Span {
+                    start: NonNull::new_unchecked(self.start.as_ptr().add(range.start)),
+                    end: NonNull::new_unchecked(self.start.as_ptr().add(range.end)),
+                })
+            }
+        } else {
+            None
+        }
+    }
+
     /// Wraps the span with improved debugging support given the containing
     /// input.
     #[inline(always)]
@@ -336,6 +403,30 @@ impl<'a> fmt::Debug for DebugFor<'a> {
     }
 }
 
+/// A [`Span`] paired with a parent, serialized as `start` and `end` offsets
+/// within the parent.
+///
+/// Created via [`Span::serialize_in()`].
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[must_use]
+#[derive(Debug, Copy, Clone)]
+pub struct SpanIn {
+    pub(crate) span: Span,
+    pub(crate) parent: Span,
+}
+
+/// Deserializes a [`Span`] from `start` and `end` offsets within a parent.
+///
+/// Created via [`Span::seed()`].
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[must_use]
+#[derive(Debug, Copy, Clone)]
+pub struct SpanSeed {
+    pub(crate) parent: Span,
+}
+
 pub trait Parent: Sized {
     fn extract(self, span: Span) -> Option<Self>;
 }
@@ -0,0 +1,217 @@
+use core::iter::FusedIterator;
+
+use crate::input::{Input, Pattern, PrivateExt};
+use crate::util::fast;
+
+/// Iterator over input separated by a pattern.
+///
+/// Created via [`Input::split()`].
+///
+/// All sections but the last are bound at both sides. The last section has the
+/// same [`Bound`] as the input that was split, as it may still change in
+/// further passes.
+///
+/// [`Bound`]: crate::input::Bound
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Split<I, P> {
+    input: Option<I>,
+    pattern: P,
+    /// Whether the last match was empty, in which case the next search must
+    /// start past the following token to make progress.
+    after_empty: bool,
+}
+
+impl<I, P> Split<I, P> {
+    pub(crate) fn new(input: I, pattern: P) -> Self {
+        Self {
+            input: Some(input),
+            pattern,
+            after_empty: false,
+        }
+    }
+}
+
+impl<'i, I, P> Iterator for Split<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+    type Item = I;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.input.take()?;
+        // Like `str::split`, step past a token after an empty match so the
+        // same empty match isn't found again.
+        let offset = if self.after_empty {
+            match input.clone().split_token_opt() {
+                Some((_, tail)) => input.byte_len() - tail.byte_len(),
+                None => return Some(input),
+            }
+        } else {
+            0
+        };
+        // SAFETY: the offset is either zero or the end of the first token.
+        let (_, window) = unsafe { input.clone().split_at_byte_unchecked(offset) };
+        match self.pattern.find_match(&window) {
+            Some((index, len)) => {
+                // SAFETY: the pattern returns a valid match within the window.
+                let (head, tail) = unsafe { input.split_at_byte_unchecked(offset + index) };
+                let (_, tail) = unsafe { tail.split_at_byte_unchecked(len) };
+                self.after_empty = len == 0;
+                self.input = Some(tail);
+                Some(head)
+            }
+            None => Some(input),
+        }
+    }
+}
+
+impl<'i, I, P> FusedIterator for Split<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+}
+
+/// Iterator over input separated by a pattern, skipping a trailing empty
+/// section.
+///
+/// Created via [`Input::split_terminator()`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitTerminator<I, P> {
+    inner: Split<I, P>,
+}
+
+impl<I, P> SplitTerminator<I, P> {
+    pub(crate) fn new(input: I, pattern: P) -> Self {
+        Self {
+            inner: Split::new(input, pattern),
+        }
+    }
+}
+
+impl<'i, I, P> Iterator for SplitTerminator<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+    type Item = I;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section = self.inner.next()?;
+        if section.is_empty() && self.inner.input.is_none() {
+            None
+        } else {
+            Some(section)
+        }
+    }
+}
+
+impl<'i, I, P> FusedIterator for SplitTerminator<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+}
+
+/// Iterator over at most `n` sections of input separated by a pattern.
+///
+/// Created via [`Input::splitn()`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitN<I, P> {
+    inner: Split<I, P>,
+    count: usize,
+}
+
+impl<I, P> SplitN<I, P> {
+    pub(crate) fn new(input: I, count: usize, pattern: P) -> Self {
+        Self {
+            inner: Split::new(input, pattern),
+            count,
+        }
+    }
+}
+
+impl<'i, I, P> Iterator for SplitN<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+    type Item = I;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.inner.input.take()
+            }
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+impl<'i, I, P> FusedIterator for SplitN<I, P>
+where
+    I: Input<'i>,
+    P: Pattern<I> + Copy,
+{
+}
+
+/// Iterator over the lines of input.
+///
+/// Created via [`Input::lines()`].
+///
+/// Lines are ended with either a newline (`\n`) or a carriage return followed
+/// by a newline (`\r\n`), neither of which are included in the line. The last
+/// line is not required to be ended, and if empty is skipped.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Lines<I> {
+    input: Option<I>,
+}
+
+impl<I> Lines<I> {
+    pub(crate) fn new(input: I) -> Self {
+        Self { input: Some(input) }
+    }
+}
+
+impl<'i, I> Iterator for Lines<I>
+where
+    I: Input<'i>,
+{
+    type Item = I;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.input.take()?;
+        match fast::find_u8_match(b'\n', input.as_dangerous_bytes()) {
+            Some(index) => {
+                // SAFETY: a newline is a single byte token, so the index and
+                // the index after it are valid token boundaries.
+                let (line, tail) = unsafe { input.split_at_byte_unchecked(index) };
+                let (_, tail) = unsafe { tail.split_at_byte_unchecked(1) };
+                self.input = Some(tail);
+                match line.as_dangerous_bytes().last() {
+                    // SAFETY: a carriage return is a single byte token, so the
+                    // index before it is a valid token boundary.
+                    Some(b'\r') => unsafe {
+                        let index = line.byte_len() - 1;
+                        Some(line.split_at_byte_unchecked(index).0)
+                    },
+                    _ => Some(line),
+                }
+            }
+            None if input.is_empty() => None,
+            None => Some(input),
+        }
+    }
+}
+
+impl<'i, I> FusedIterator for Lines<I> where I: Input<'i> {}
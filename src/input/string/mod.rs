@@ -1,6 +1,7 @@
 mod maybe;
 mod pattern;
 mod prefix;
+mod suffix;
 
 use core::str;
 
@@ -69,6 +70,7 @@ impl<'i> String<'i> {
         if self.is_empty() {
             Err(E::from(ExpectedLength {
                 len: Length::AtLeast(1),
+                found: None,
                 context: CoreContext {
                     span: self.span(),
                     operation: CoreOperation::IntoString,
@@ -1,9 +1,9 @@
 // # Safety note about searching for substrings for `Pattern`.
 //
 // As we are looking for exact slice matches, the needle and haystack are both
-// UTF-8, we are searching forward and the fact the first byte of a UTF-8
-// character cannot clash with a continuation byte we can safely search as just
-// raw bytes and return those indexes.
+// UTF-8 and the fact the first byte of a UTF-8 character cannot clash with a
+// continuation byte we can safely search (forward or in reverse) as just raw
+// bytes and return those indexes.
 //
 // For searching within a string we care that we don't return an index that
 // isn't a char boundary, but with the above conditions this is impossible.
@@ -21,7 +21,9 @@
 // | 3      | 1110xxxx | 10xxxxxx | 10xxxxxx |          |
 // | 4      | 11110xxx | 10xxxxxx | 10xxxxxx | 10xxxxxx |
 
-use crate::input::{Pattern, String};
+#[cfg(feature = "alloc")]
+use crate::input::Needles;
+use crate::input::{ByteSet, Pattern, String};
 use crate::util::fast;
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -31,25 +33,21 @@ unsafe impl<'i, F> Pattern<String<'i>> for F
 where
     F: FnMut(char) -> bool,
 {
-    fn find_match(mut self, input: &String<'i>) -> Option<(usize, usize)> {
-        for (i, c) in input.as_dangerous().char_indices() {
-            if !(self)(c) {
-                return Some((i, c.len_utf8()));
-            }
-        }
-        None
+    fn find_match(self, input: &String<'i>) -> Option<(usize, usize)> {
+        fast::find_char_by(input.as_dangerous(), self).map(|(i, c)| (i, c.len_utf8()))
+    }
+
+    fn find_match_rev(mut self, input: &String<'i>) -> Option<(usize, usize)> {
+        input
+            .as_dangerous()
+            .char_indices()
+            .rev()
+            .find(|(_, c)| (self)(*c))
+            .map(|(i, c)| (i, c.len_utf8()))
     }
 
     fn find_reject(mut self, input: &String<'i>) -> Option<usize> {
-        input.as_dangerous().char_indices().find_map(
-            |(i, b)| {
-                if (self)(b) {
-                    None
-                } else {
-                    Some(i)
-                }
-            },
-        )
+        fast::find_char_by(input.as_dangerous(), |c| !(self)(c)).map(|(i, _)| i)
     }
 }
 
@@ -62,6 +60,11 @@ unsafe impl<'i> Pattern<String<'i>> for char {
             .map(|index| (index, self.len_utf8()))
     }
 
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        fast::find_char_match_rev(self, input.as_dangerous().as_bytes())
+            .map(|index| (index, self.len_utf8()))
+    }
+
     fn find_reject(self, input: &String<'i>) -> Option<usize> {
         fast::find_char_reject(self, input.as_dangerous().as_bytes())
     }
@@ -77,12 +80,73 @@ unsafe impl<'i> Pattern<String<'i>> for &str {
             .map(|index| (index, self.len()))
     }
 
+    #[inline]
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        fast::find_slice_match_rev(self.as_bytes(), input.as_dangerous().as_bytes())
+            .map(|index| (index, self.len()))
+    }
+
     #[inline]
     fn find_reject(self, input: &String<'i>) -> Option<usize> {
         fast::find_slice_reject(self.as_bytes(), input.as_dangerous().as_bytes())
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Set pattern
+//
+// Only ASCII bytes of a set are matched and needles that are not valid UTF-8
+// are skipped, so as with sub-slices the indexes are always char boundaries.
+
+unsafe impl<'i> Pattern<String<'i>> for ByteSet<'_> {
+    fn find_match(self, input: &String<'i>) -> Option<(usize, usize)> {
+        let bytes = input.as_dangerous().as_bytes();
+        if self.is_ascii() {
+            fast::find_set_match(self, bytes).map(|index| (index, 1))
+        } else {
+            bytes
+                .iter()
+                .position(|b| b.is_ascii() && self.contains(*b))
+                .map(|index| (index, 1))
+        }
+    }
+
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        let bytes = input.as_dangerous().as_bytes();
+        if self.is_ascii() {
+            fast::find_set_match_rev(self, bytes).map(|index| (index, 1))
+        } else {
+            bytes
+                .iter()
+                .rposition(|b| b.is_ascii() && self.contains(*b))
+                .map(|index| (index, 1))
+        }
+    }
+
+    fn find_reject(self, input: &String<'i>) -> Option<usize> {
+        input
+            .as_dangerous()
+            .as_bytes()
+            .iter()
+            .position(|b| !b.is_ascii() || !self.contains(*b))
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<'i> Pattern<String<'i>> for &Needles {
+    fn find_match(self, input: &String<'i>) -> Option<(usize, usize)> {
+        Needles::find_match(self, input.as_dangerous().as_bytes(), true)
+    }
+
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        Needles::find_match_rev(self, input.as_dangerous().as_bytes(), true)
+    }
+
+    fn find_reject(self, input: &String<'i>) -> Option<usize> {
+        Needles::find_reject(self, input.as_dangerous().as_bytes(), true)
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Regex pattern
 
@@ -92,6 +156,18 @@ unsafe impl<'i> Pattern<String<'i>> for &regex::Regex {
         regex::Regex::find(self, input.as_dangerous()).map(|m| (m.start(), m.end() - m.start()))
     }
 
+    fn find_match_rev(self, input: &String<'i>) -> Option<(usize, usize)> {
+        // The rightmost match may overlap the last non-overlapping match, so
+        // search backwards from its start for the last index a match starts.
+        let haystack = input.as_dangerous();
+        let last = regex::Regex::find_iter(self, haystack).last()?;
+        (last.start()..=haystack.len())
+            .rev()
+            .filter(|&i| haystack.is_char_boundary(i))
+            .find_map(|i| regex::Regex::find_at(self, haystack, i).filter(|m| m.start() == i))
+            .map(|m| (m.start(), m.end() - m.start()))
+    }
+
     fn find_reject(self, input: &String<'i>) -> Option<usize> {
         let mut maybe_reject = 0;
         loop {
@@ -0,0 +1,18 @@
+use crate::input::{String, Suffix};
+
+unsafe impl<'i> Suffix<String<'i>> for char {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &String<'i>) -> bool {
+        match input.as_dangerous().chars().next_back() {
+            Some(c) => c == *self,
+            None => false,
+        }
+    }
+}
+
+unsafe impl<'i> Suffix<String<'i>> for str {
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &String<'i>) -> bool {
+        input.as_dangerous().ends_with(self)
+    }
+}
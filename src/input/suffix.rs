@@ -0,0 +1,23 @@
+use super::ByteLength;
+
+/// Implemented for types that can be a suffix for a given input.
+///
+/// # Safety
+///
+/// The implementation **must** guarantee that the value returned is correct as
+/// it is used for unchecked memory operations and an incorrect implementation
+/// would introduce invalid memory access.
+pub unsafe trait Suffix<I>: ByteLength {
+    /// Returns `true` if `self` is a suffix of the given input.
+    fn is_suffix_of(&self, input: &I) -> bool;
+}
+
+unsafe impl<T: ?Sized, I> Suffix<I> for &T
+where
+    T: Suffix<I>,
+{
+    #[inline(always)]
+    fn is_suffix_of(&self, input: &I) -> bool {
+        T::is_suffix_of(self, input)
+    }
+}
@@ -9,7 +9,10 @@ use crate::fmt::{Debug, Display, DisplayBase};
 use crate::input::pattern::Pattern;
 use crate::reader::Reader;
 
-use super::{Bound, ByteLength, Bytes, MaybeString, Prefix, Span, String, Token};
+use super::{
+    Bound, ByteLength, Bytes, Lines, MaybeString, Prefix, Span, Split, SplitN, SplitTerminator,
+    String, Suffix, Token,
+};
 
 /// Implemented for immutable wrappers around bytes to be processed ([`Bytes`]/[`String`]).
 ///
@@ -124,6 +127,56 @@ pub trait Input<'i>: Private<'i, Self::Token> {
         }
     }
 
+    /// Splits the input into two at the start of the last match of a pattern.
+    ///
+    /// Returns `None` if the pattern was not found or the end of the input is
+    /// not bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let input = dangerous::input("archive.tar.gz").into_bound();
+    /// let (name, ext) = input.split_at_last('.').unwrap();
+    ///
+    /// assert_eq!(name, "archive.tar");
+    /// assert_eq!(ext, ".gz");
+    /// ```
+    #[inline]
+    fn split_at_last<P>(self, pattern: P) -> Option<(Self, Self)>
+    where
+        P: Pattern<Self>,
+    {
+        self.split_until_last_opt(pattern)
+    }
+
+    /// Returns the input with a suffix removed.
+    ///
+    /// Returns `None` if the input does not end with the suffix or the end of
+    /// the input is not bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let input = dangerous::input(b"hello\r\n").into_bound();
+    ///
+    /// assert_eq!(input.clone().strip_suffix(b"\r\n").unwrap(), b"hello"[..]);
+    /// assert!(input.strip_suffix(b'\r').is_none());
+    /// ```
+    #[inline]
+    fn strip_suffix<P>(self, suffix: P) -> Option<Self>
+    where
+        P: Suffix<Self>,
+    {
+        match self.split_suffix_opt(suffix) {
+            (head, Some(_)) => Some(head),
+            (_, None) => None,
+        }
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // Provided methods
 
@@ -175,6 +228,97 @@ pub trait Input<'i>: Private<'i, Self::Token> {
         self.clone().tokens().next_back()
     }
 
+    /// Returns an iterator over sections of the input separated by a pattern.
+    ///
+    /// The pattern is not included in the sections. If the pattern is not
+    /// found, the only section returned is the input itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let mut split = dangerous::input(b"a,b,").split(b',');
+    ///
+    /// assert_eq!(split.next().unwrap(), b"a"[..]);
+    /// assert_eq!(split.next().unwrap(), b"b"[..]);
+    /// assert_eq!(split.next().unwrap(), b""[..]);
+    /// assert!(split.next().is_none());
+    /// ```
+    #[inline(always)]
+    fn split<P>(self, pattern: P) -> Split<Self, P>
+    where
+        P: Pattern<Self> + Copy,
+    {
+        Split::new(self, pattern)
+    }
+
+    /// Returns an iterator over sections of the input separated by a pattern,
+    /// skipping the last section if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let mut split = dangerous::input(b"a,b,").split_terminator(b',');
+    ///
+    /// assert_eq!(split.next().unwrap(), b"a"[..]);
+    /// assert_eq!(split.next().unwrap(), b"b"[..]);
+    /// assert!(split.next().is_none());
+    /// ```
+    #[inline(always)]
+    fn split_terminator<P>(self, pattern: P) -> SplitTerminator<Self, P>
+    where
+        P: Pattern<Self> + Copy,
+    {
+        SplitTerminator::new(self, pattern)
+    }
+
+    /// Returns an iterator over at most `n` sections of the input separated by
+    /// a pattern.
+    ///
+    /// The last section returned contains the remaining input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let mut split = dangerous::input("a=b=c").splitn(2, '=');
+    ///
+    /// assert_eq!(split.next().unwrap(), "a");
+    /// assert_eq!(split.next().unwrap(), "b=c");
+    /// assert!(split.next().is_none());
+    /// ```
+    #[inline(always)]
+    fn splitn<P>(self, n: usize, pattern: P) -> SplitN<Self, P>
+    where
+        P: Pattern<Self> + Copy,
+    {
+        SplitN::new(self, n, pattern)
+    }
+
+    /// Returns an iterator over the lines of the input.
+    ///
+    /// See [`Lines`] for how lines are ended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Input;
+    ///
+    /// let mut lines = dangerous::input("a\r\nb\n").lines();
+    ///
+    /// assert_eq!(lines.next().unwrap(), "a");
+    /// assert_eq!(lines.next().unwrap(), "b");
+    /// assert!(lines.next().is_none());
+    /// ```
+    #[inline(always)]
+    fn lines(self) -> Lines<Self> {
+        Lines::new(self)
+    }
+
     /// Create a reader with the expectation all of the input is read.
     ///
     /// # Errors
@@ -196,6 +340,7 @@ pub trait Input<'i>: Private<'i, Self::Token> {
             Ok(ok) if r.at_end() => Ok(ok),
             Ok(_) => Err(E::from(ExpectedLength {
                 len: Length::Exactly(0),
+                found: None,
                 context: CoreContext {
                     span: r.take_remaining().span(),
                     operation: CoreOperation::ReadAll,
@@ -275,6 +420,7 @@ pub trait Input<'i>: Private<'i, Self::Token> {
         if self.is_empty() {
             Err(E::from(ExpectedLength {
                 len: Length::AtLeast(1),
+                found: None,
 
                 context: CoreContext {
                     span: self.span(),
@@ -350,6 +496,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         self.clone().split_at_opt(mid).ok_or_else(|| {
             E::from(ExpectedLength {
                 len: Length::AtLeast(mid),
+                found: None,
 
                 context: CoreContext {
                     span: self.span(),
@@ -376,6 +523,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         if self.byte_len() < mid {
             Err(E::from(ExpectedLength {
                 len: Length::AtLeast(mid),
+                found: None,
 
                 context: CoreContext {
                     span: self.span(),
@@ -429,6 +577,7 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         self.clone().split_token_opt().ok_or_else(|| {
             E::from(ExpectedLength {
                 len: Length::AtLeast(1),
+                found: None,
 
                 context: CoreContext {
                     span: self.span(),
@@ -440,6 +589,25 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         })
     }
 
+    /// Returns an error for when the end of the input must be bound to
+    /// continue, requesting more input.
+    #[inline(always)]
+    fn expected_end<E>(self, operation: CoreOperation, expected: &'static str) -> E
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        E::from(ExpectedLength {
+            len: Length::AtLeast(self.byte_len().saturating_add(1)),
+            found: None,
+            context: CoreContext {
+                span: self.span(),
+                operation,
+                expected: CoreExpected::EnoughInputFor(expected),
+            },
+            input: self.into_maybe_string(),
+        })
+    }
+
     /// Splits a prefix from the input if it is present.
     #[inline(always)]
     fn split_prefix_opt<P>(self, prefix: P) -> (Option<Self>, Self)
@@ -490,6 +658,65 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         }
     }
 
+    /// Splits a suffix from the input if it is present.
+    ///
+    /// A suffix is only matched if the end of the input is bound, as more
+    /// input could follow it otherwise.
+    #[inline(always)]
+    fn split_suffix_opt<P>(self, suffix: P) -> (Self, Option<Self>)
+    where
+        P: Suffix<Self>,
+    {
+        if self.is_bound() && suffix.is_suffix_of(&self) {
+            // SAFETY: we just validated that suffix is within the input so the
+            // input length less its length is a valid index.
+            let mid = self.byte_len() - suffix.byte_len();
+            let (head, tail) = unsafe { self.split_at_byte_unchecked(mid) };
+            (head, Some(tail))
+        } else {
+            (self, None)
+        }
+    }
+
+    /// Splits a suffix from the input if it is present.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input does not have the suffix, or the end of
+    /// the input is not bound.
+    #[inline(always)]
+    fn split_suffix_for<P, E>(self, suffix: P, operation: CoreOperation) -> Result<(Self, Self), E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+        P: Suffix<Self> + Into<Value<'i>>,
+    {
+        if !self.is_bound() {
+            return Err(self.expected_end(operation, "suffix"));
+        }
+        match self.clone().split_suffix_opt(&suffix) {
+            (head, Some(tail)) => Ok((head, tail)),
+            (unmatched, None) => {
+                let bytes = unmatched.as_dangerous_bytes();
+                let suffix_len = suffix.byte_len();
+                let actual = if bytes.len() > suffix_len {
+                    &bytes[bytes.len() - suffix_len..]
+                } else {
+                    bytes
+                };
+                Err(E::from(ExpectedValue {
+                    expected: suffix.into(),
+                    context: CoreContext {
+                        span: actual.into(),
+                        operation,
+                        expected: CoreExpected::ExactValue,
+                    },
+                    input: self.into_maybe_string(),
+                }))
+            }
+        }
+    }
+
     /// Splits at a pattern in the input if it is present.
     #[inline(always)]
     fn split_until_opt<P>(self, pattern: P) -> Option<(Self, Self)>
@@ -536,6 +763,57 @@ pub(crate) trait PrivateExt<'i>: Input<'i> {
         })
     }
 
+    /// Splits at the last match of a pattern in the input if it is present.
+    ///
+    /// The last match is only searched for if the end of the input is bound,
+    /// as a later match could follow otherwise.
+    #[inline(always)]
+    fn split_until_last_opt<P>(self, pattern: P) -> Option<(Self, Self)>
+    where
+        P: Pattern<Self>,
+    {
+        if !self.is_bound() {
+            return None;
+        }
+        pattern.find_match_rev(&self).map(|(index, _)| {
+            // SAFETY: Pattern guarantees it returns valid indexes.
+            unsafe { self.split_at_byte_unchecked(index) }
+        })
+    }
+
+    /// Splits the input up to the last match of a pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input does not have the pattern, or the end of
+    /// the input is not bound.
+    #[inline(always)]
+    fn split_until_last_for<P, E>(
+        self,
+        pattern: P,
+        operation: CoreOperation,
+    ) -> Result<(Self, Self), E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+        P: Pattern<Self> + Into<Value<'i>> + Copy,
+    {
+        if !self.is_bound() {
+            return Err(self.expected_end(operation, "last match"));
+        }
+        self.clone().split_until_last_opt(pattern).ok_or_else(|| {
+            E::from(ExpectedValue {
+                expected: pattern.into(),
+                context: CoreContext {
+                    span: self.span(),
+                    operation,
+                    expected: CoreExpected::PatternMatch,
+                },
+                input: self.into_maybe_string(),
+            })
+        })
+    }
+
     /// Splits at a pattern in the input if it is present.
     ///
     /// # Errors
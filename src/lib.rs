@@ -22,9 +22,17 @@
 //! | `simd`           | **Enabled** | Enables all supported SIMD optimisations.          |
 //! | `unicode`        | **Enabled** | Enables improved unicode printing support.         |
 //! | `full-backtrace` | **Enabled** | Enables collection of all contexts for `Expected`. |
+//! | `color`          | _Disabled_  | Enables ANSI colour output support for displays.   |
+//! | `json`           | _Disabled_  | Enables JSON output support for errors.            |
+//! | `derive`         | _Disabled_  | Enables `#[derive(Read)]` support.                 |
+//! | `futures`        | _Disabled_  | Enables `futures` async stream decoding support.   |
+//! | `tokio`          | _Disabled_  | Enables `tokio-util` codec support.                |
 //! | `zc`             | _Disabled_  | Enables `zc` crate support.                        |
+//! | `bytes`          | _Disabled_  | Enables `bytes` crate support.                     |
 //! | `nom`            | _Disabled_  | Enables `nom` crate error support.                 |
-//! | `regex`          | _Disabled_  | Enables `regex` pattern support.                   |
+//! | `regex`          | _Disabled_  | Enables `regex` pattern and capture support.       |
+//! | `aho-corasick`   | _Disabled_  | Enables Aho-Corasick `Needles` search (Rust 1.60). |
+//! | `serde`          | _Disabled_  | Enables `serde` crate support.                     |
 
 ///////////////////////////////////////////////////////////////////////////////
 // Library quirks & hacks
@@ -71,10 +79,23 @@ mod util;
 pub mod display;
 pub mod error;
 pub mod input;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub mod serde;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod stream;
 
 pub use self::error::{Error, Expected, Fatal, Invalid, ToRetryRequirement};
 pub use self::input::{Bound, ByteArray, Bytes, Input, MaybeString, Span, String};
-pub use self::reader::{BytesReader, Peek, Reader, StringReader};
+pub use self::reader::{
+    BitReader, BytesReader, ChainReader, ManyUntil, Peek, Reader, Repeat, RepeatN, Separated,
+    StringReader,
+};
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use dangerous_derive::Read;
 
 // Re-exported types from core::fmt along with `DisplayBase` and `Write`.
 // This is used crate wide with the exception of crate::display.
@@ -0,0 +1,289 @@
+use core::marker::PhantomData;
+
+use crate::error::{
+    with_context, Context, CoreContext, CoreExpected, CoreOperation, ExpectedLength, ExpectedValid,
+    Length, WithContext,
+};
+use crate::fmt;
+use crate::input::{BitOrder, BitSpan, Bytes, Input, Private};
+
+/// Reads bits from [`Bytes`].
+///
+/// Created via [`BytesReader::bits()`], which realigns the outer reader to the
+/// next byte boundary once finished.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Input, Invalid};
+/// use dangerous::input::BitOrder;
+///
+/// let result: Result<_, Invalid> = dangerous::input(&[0b1011_0010]).read_all(|r| {
+///     r.bits(BitOrder::MsbFirst, |r| {
+///         let flag = r.read_bit()?;
+///         let kind = r.read_bits(3)?;
+///         Ok((flag, kind))
+///     })
+/// });
+///
+/// assert_eq!(result.unwrap(), (true, 0b011));
+/// ```
+///
+/// [`BytesReader::bits()`]: crate::BytesReader::bits()
+pub struct BitReader<'i, E> {
+    /// Remaining input, where the first byte may be partially read.
+    input: Bytes<'i>,
+    /// The number of bits read from the first byte of the input.
+    offset: u8,
+    order: BitOrder,
+    types: PhantomData<E>,
+}
+
+impl<'i, E> BitReader<'i, E> {
+    pub(crate) fn new(input: Bytes<'i>, order: BitOrder) -> Self {
+        Self {
+            input,
+            offset: 0,
+            order,
+            types: PhantomData,
+        }
+    }
+
+    /// Consumes the reader, returning the input after the next byte boundary.
+    pub(crate) fn into_aligned(mut self) -> Bytes<'i> {
+        self.align();
+        self.input
+    }
+
+    /// Returns the [`BitOrder`] bits are read in.
+    #[must_use]
+    #[inline(always)]
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns `true` if the `BitReader` has no more bits to read.
+    #[must_use]
+    #[inline(always)]
+    pub fn at_end(&self) -> bool {
+        self.remaining_bits() == 0
+    }
+
+    /// Returns the number of bits left within the reader.
+    #[must_use]
+    pub fn remaining_bits(&self) -> usize {
+        self.input.len() * 8 - usize::from(self.offset)
+    }
+
+    /// Returns `true` if the reader is positioned on a byte boundary.
+    #[must_use]
+    #[inline(always)]
+    pub fn is_aligned(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Returns the number of bits already read from the current byte.
+    ///
+    /// This value is always less than `8`.
+    #[must_use]
+    #[inline(always)]
+    pub fn bit_offset(&self) -> u8 {
+        self.offset
+    }
+
+    /// Skips any bits left in the current byte, positioning the reader on the
+    /// next byte boundary.
+    pub fn align(&mut self) {
+        if self.offset != 0 {
+            // SAFETY: a non zero offset means the first byte of the input
+            // exists.
+            let (_, tail) = unsafe { self.input.clone().split_at_byte_unchecked(1) };
+            self.input = tail;
+            self.offset = 0;
+        }
+    }
+
+    /// Use the `BitReader` in a mutable context.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the provided function with the specified
+    /// context attached.
+    #[inline(always)]
+    pub fn context<F, T>(&mut self, context: impl Context, f: F) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        with_context(context, self.input.clone(), || f(self))
+    }
+
+    /// Read a single bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no more bits.
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool, E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+
+    /// Read `len` bits as an unsigned integer.
+    ///
+    /// Reading zero bits always succeeds, returning `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there are not enough bits and
+    /// [`ExpectedValid`] if `len` is greater than `64`.
+    pub fn read_bits(&mut self, len: u32) -> Result<u64, E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        self.split_bits_for(len, CoreOperation::ReadBits)
+            .map(|(value, _)| value)
+    }
+
+    /// Read `len` bits as an unsigned integer along with the [`BitSpan`] they
+    /// were read from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there are not enough bits and
+    /// [`ExpectedValid`] if `len` is greater than `64`.
+    pub fn read_bits_spanned(&mut self, len: u32) -> Result<(u64, BitSpan), E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        self.split_bits_for(len, CoreOperation::ReadBits)
+    }
+
+    /// Skip `len` bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are not enough bits.
+    pub fn skip_bits(&mut self, len: usize) -> Result<(), E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        let end = len.saturating_add(usize::from(self.offset));
+        match self.input.clone().split_at_opt(end / 8) {
+            // A partial end byte must also exist to land within it.
+            Some((_, tail)) if end % 8 == 0 || !tail.is_empty() => {
+                self.input = tail;
+                #[allow(clippy::cast_possible_truncation)]
+                let offset = (end % 8) as u8;
+                self.offset = offset;
+                Ok(())
+            }
+            _ => Err(self.expected_bits(end, CoreOperation::SkipBits)),
+        }
+    }
+
+    /// Peek `len` bits as an unsigned integer without mutating the
+    /// `BitReader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there are not enough bits and
+    /// [`ExpectedValid`] if `len` is greater than `64`.
+    pub fn peek_bits(&self, len: u32) -> Result<u64, E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        let mut peek = Self::new(self.input.clone(), self.order);
+        peek.offset = self.offset;
+        peek.split_bits_for(len, CoreOperation::PeekBits)
+            .map(|(value, _)| value)
+    }
+
+    fn split_bits_for(&mut self, len: u32, operation: CoreOperation) -> Result<(u64, BitSpan), E>
+    where
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        if len > u64::BITS {
+            return Err(E::from(ExpectedValid {
+                retry_requirement: None,
+                context: CoreContext {
+                    span: self.input.span().start(),
+                    operation,
+                    expected: CoreExpected::Valid("bit length of at most 64"),
+                },
+                input: self.input.clone().into_maybe_string(),
+            }));
+        }
+        let start = self.offset;
+        if len == 0 {
+            return Ok((0, BitSpan::new(self.input.span().start(), start, 0)));
+        }
+        let end = usize::from(start) + len as usize;
+        let byte_len = (end + 7) / 8;
+        let bytes = match self.input.as_dangerous().get(..byte_len) {
+            Some(bytes) => bytes,
+            None => return Err(self.expected_bits(end, operation)),
+        };
+        let mut value = 0_u64;
+        let mut read = 0;
+        let mut offset = u32::from(start);
+        for &byte in bytes {
+            let take = (8 - offset).min(len - read);
+            let mask = u8::MAX >> (8 - take);
+            value = match self.order {
+                BitOrder::MsbFirst => {
+                    let chunk = (byte >> (8 - offset - take)) & mask;
+                    (value << take) | u64::from(chunk)
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (byte >> offset) & mask;
+                    value | (u64::from(chunk) << read)
+                }
+            };
+            read += take;
+            offset = 0;
+        }
+        let span = BitSpan::new(bytes.into(), start, len as usize);
+        // SAFETY: `end / 8` is at most `byte_len` which we checked is within
+        // the input.
+        let (_, tail) = unsafe { self.input.clone().split_at_byte_unchecked(end / 8) };
+        self.input = tail;
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (end % 8) as u8;
+        self.offset = offset;
+        Ok((value, span))
+    }
+
+    fn expected_bits<SE>(&self, end: usize, operation: CoreOperation) -> SE
+    where
+        SE: From<ExpectedLength<'i>>,
+    {
+        SE::from(ExpectedLength {
+            len: Length::AtLeast(end.saturating_add(7) / 8),
+            found: None,
+            context: CoreContext {
+                span: self.input.span(),
+                operation,
+                expected: CoreExpected::EnoughInputFor("bits"),
+            },
+            input: self.input.clone().into_maybe_string(),
+        })
+    }
+}
+
+impl<'i, E> fmt::Debug for BitReader<'i, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitReader")
+            .field("input", &self.input)
+            .field("bit_offset", &self.offset)
+            .field("order", &self.order)
+            .finish()
+    }
+}
@@ -1,14 +1,21 @@
-use crate::error::{CoreOperation, ExpectedLength, ExpectedValid, WithContext};
-use crate::input::{ByteArray, Bytes, String};
+use core::mem;
 
-use super::BytesReader;
+use crate::error::{
+    with_context, CoreContext, CoreExpected, CoreNumber, CoreOperation, ExpectedLength,
+    ExpectedValid, WithContext,
+};
+use crate::input::{BitOrder, ByteArray, Bytes, Endian, FloatPolicy, Input, LengthPrefix, String};
+
+use super::{BitReader, BytesReader};
 
 impl<'i, E> BytesReader<'i, E> {
     /// Read an array from input.
     ///
     /// # Integers
     ///
-    /// This function can be used to read integers like so:
+    /// Integers are best read with the dedicated functions such as
+    /// [`BytesReader::read_u32_le()`], but this function can also be used to
+    /// read them like so:
     ///
     /// ```
     /// use dangerous::{Input, ByteArray, Invalid};
@@ -39,6 +46,141 @@ impl<'i, E> BytesReader<'i, E> {
         self.advance_opt(Bytes::split_array_opt)
     }
 
+    /// Read a length header followed by a length of input.
+    ///
+    /// The reader is left untouched if either the header or the input could
+    /// not be read, so partial frames can be retried once more input arrives.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use dangerous::input::{Endian, LengthPrefix};
+    ///
+    /// let input = dangerous::input(&[0x00, 0x02, b'h', b'i']);
+    /// let result: Result<_, Invalid> = input.read_all(|r| {
+    ///     r.take_length_prefixed(LengthPrefix::U16(Endian::Big))
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"hi"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there is not enough input for the header
+    /// or the length it specifies and [`ExpectedValid`] if the header was
+    /// invalid or does not fit within a `usize`.
+    pub fn take_length_prefixed(&mut self, prefix: LengthPrefix) -> Result<Bytes<'i>, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        self.take_length_prefixed_max(prefix, usize::MAX)
+    }
+
+    /// Read a length header followed by a length of input, where the length
+    /// must not exceed `max`.
+    ///
+    /// See [`BytesReader::take_length_prefixed()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedLength`] if there is not enough input for the header
+    /// or the length it specifies and [`ExpectedValid`] if the header was
+    /// invalid or the length exceeds `max`.
+    pub fn take_length_prefixed_max(
+        &mut self,
+        prefix: LengthPrefix,
+        max: usize,
+    ) -> Result<Bytes<'i>, E>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedLength<'i>>,
+        E: From<ExpectedValid<'i>>,
+    {
+        self.try_advance(|input| {
+            with_context(
+                CoreContext::from_operation(CoreOperation::TakeLengthPrefixed, input.span()),
+                input.clone(),
+                || {
+                    let mut r = Self::new(input.clone());
+                    let len = match prefix {
+                        LengthPrefix::U8 => r.read_u8().map(u64::from),
+                        LengthPrefix::U16(Endian::Little) => r.read_u16_le().map(u64::from),
+                        LengthPrefix::U16(Endian::Big) => r.read_u16_be().map(u64::from),
+                        LengthPrefix::U16(Endian::Native) => r.read_u16_ne().map(u64::from),
+                        LengthPrefix::U32(Endian::Little) => r.read_u32_le().map(u64::from),
+                        LengthPrefix::U32(Endian::Big) => r.read_u32_be().map(u64::from),
+                        LengthPrefix::U32(Endian::Native) => r.read_u32_ne().map(u64::from),
+                        LengthPrefix::U64(Endian::Little) => r.read_u64_le(),
+                        LengthPrefix::U64(Endian::Big) => r.read_u64_be(),
+                        LengthPrefix::U64(Endian::Native) => r.read_u64_ne(),
+                        LengthPrefix::Uleb128 => r.read_uleb128_u64(),
+                    }?;
+                    match usize::try_from(len) {
+                        Ok(len) if len <= max => r.take(len).map(|taken| (taken, r.input)),
+                        _ => {
+                            let header_len = input.len() - r.remaining_bytes();
+                            Err(E::from(ExpectedValid {
+                                retry_requirement: None,
+                                context: CoreContext {
+                                    span: input.as_dangerous()[..header_len].into(),
+                                    operation: CoreOperation::TakeLengthPrefixed,
+                                    expected: CoreExpected::Valid("length within the maximum"),
+                                },
+                                input: input.into_maybe_string(),
+                            }))
+                        }
+                    }
+                },
+            )
+        })
+    }
+
+    /// Read input as bits with a [`BitReader`].
+    ///
+    /// Once the provided function returns successfully, the reader is advanced
+    /// to the next byte boundary after the last bit read. If the function
+    /// returns an error, the reader is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use dangerous::input::BitOrder;
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(&[0b0000_0101, 0xff]).read_all(|r| {
+    ///     let header = r.bits(BitOrder::LsbFirst, |r| r.read_bits(3))?;
+    ///     // The remaining 5 bits of the first byte are skipped.
+    ///     let next = r.read_u8()?;
+    ///     Ok((header, next))
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), (0b101, 0xff));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error the provided function does.
+    pub fn bits<F, T>(&mut self, order: BitOrder, f: F) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        F: FnOnce(&mut BitReader<'i, E>) -> Result<T, E>,
+    {
+        self.try_advance(|input| {
+            with_context(
+                CoreContext::from_operation(CoreOperation::ReadAsBits, input.span()),
+                input.clone(),
+                || {
+                    let mut r = BitReader::new(input.clone(), order);
+                    let ok = f(&mut r)?;
+                    Ok((ok, r.into_aligned()))
+                },
+            )
+        })
+    }
+
     /// Read the remaining string input.
     ///
     /// # Errors
@@ -120,3 +262,274 @@ impl<'i, E> BytesReader<'i, E> {
             .map(drop)
     }
 }
+
+macro_rules! impl_read_num {
+    ($(
+        $ty:ident, $from:ident, $num:expr, $desc:literal =>
+        $read:ident, $read_opt:ident, $peek:ident, $peek_opt:ident;
+    )*) => {
+        impl<'i, E> BytesReader<'i, E> {
+            $(
+                #[doc = concat!("Read ", $desc, ".")]
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if there is not enough input to read the
+                /// number.
+                #[inline]
+                pub fn $read(&mut self) -> Result<$ty, E>
+                where
+                    E: From<ExpectedLength<'i>>,
+                {
+                    self.try_advance(|input| input.split_array(CoreOperation::ReadNum($num)))
+                        .map(|arr| $ty::$from(arr.into_dangerous()))
+                }
+
+                #[doc = concat!("Optionally read ", $desc, ".")]
+                ///
+                /// Returns `Some` if there was enough input, `None` if not.
+                #[inline]
+                pub fn $read_opt(&mut self) -> Option<$ty> {
+                    self.advance_opt(Bytes::split_array_opt)
+                        .map(|arr| $ty::$from(arr.into_dangerous()))
+                }
+
+                #[doc = concat!("Peek ", $desc, " without mutating the `Reader`.")]
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if there is not enough input to peek the
+                /// number.
+                #[inline]
+                pub fn $peek(&self) -> Result<$ty, E>
+                where
+                    E: From<ExpectedLength<'i>>,
+                {
+                    self.input
+                        .clone()
+                        .split_array(CoreOperation::PeekNum($num))
+                        .map(|(arr, _)| $ty::$from(arr.into_dangerous()))
+                }
+
+                #[doc = concat!("Optionally peek ", $desc, " without mutating the `Reader`.")]
+                ///
+                /// Returns `Some` if there was enough input, `None` if not.
+                #[inline]
+                #[must_use = "peek result must be used"]
+                pub fn $peek_opt(&self) -> Option<$ty> {
+                    self.input
+                        .clone()
+                        .split_array_opt()
+                        .map(|(arr, _)| $ty::$from(arr.into_dangerous()))
+                }
+            )*
+        }
+    };
+}
+
+impl_read_num! {
+    u8, from_ne_bytes, CoreNumber::U8, "a `u8`" =>
+        read_u8, read_u8_opt, peek_u8, peek_u8_opt;
+    i8, from_ne_bytes, CoreNumber::I8, "an `i8`" =>
+        read_i8, read_i8_opt, peek_i8, peek_i8_opt;
+    u16, from_le_bytes, CoreNumber::U16(Endian::Little), "a `u16` (little endian)" =>
+        read_u16_le, read_u16_le_opt, peek_u16_le, peek_u16_le_opt;
+    u16, from_be_bytes, CoreNumber::U16(Endian::Big), "a `u16` (big endian)" =>
+        read_u16_be, read_u16_be_opt, peek_u16_be, peek_u16_be_opt;
+    u16, from_ne_bytes, CoreNumber::U16(Endian::Native), "a `u16` (native endian)" =>
+        read_u16_ne, read_u16_ne_opt, peek_u16_ne, peek_u16_ne_opt;
+    i16, from_le_bytes, CoreNumber::I16(Endian::Little), "an `i16` (little endian)" =>
+        read_i16_le, read_i16_le_opt, peek_i16_le, peek_i16_le_opt;
+    i16, from_be_bytes, CoreNumber::I16(Endian::Big), "an `i16` (big endian)" =>
+        read_i16_be, read_i16_be_opt, peek_i16_be, peek_i16_be_opt;
+    i16, from_ne_bytes, CoreNumber::I16(Endian::Native), "an `i16` (native endian)" =>
+        read_i16_ne, read_i16_ne_opt, peek_i16_ne, peek_i16_ne_opt;
+    u32, from_le_bytes, CoreNumber::U32(Endian::Little), "a `u32` (little endian)" =>
+        read_u32_le, read_u32_le_opt, peek_u32_le, peek_u32_le_opt;
+    u32, from_be_bytes, CoreNumber::U32(Endian::Big), "a `u32` (big endian)" =>
+        read_u32_be, read_u32_be_opt, peek_u32_be, peek_u32_be_opt;
+    u32, from_ne_bytes, CoreNumber::U32(Endian::Native), "a `u32` (native endian)" =>
+        read_u32_ne, read_u32_ne_opt, peek_u32_ne, peek_u32_ne_opt;
+    i32, from_le_bytes, CoreNumber::I32(Endian::Little), "an `i32` (little endian)" =>
+        read_i32_le, read_i32_le_opt, peek_i32_le, peek_i32_le_opt;
+    i32, from_be_bytes, CoreNumber::I32(Endian::Big), "an `i32` (big endian)" =>
+        read_i32_be, read_i32_be_opt, peek_i32_be, peek_i32_be_opt;
+    i32, from_ne_bytes, CoreNumber::I32(Endian::Native), "an `i32` (native endian)" =>
+        read_i32_ne, read_i32_ne_opt, peek_i32_ne, peek_i32_ne_opt;
+    u64, from_le_bytes, CoreNumber::U64(Endian::Little), "a `u64` (little endian)" =>
+        read_u64_le, read_u64_le_opt, peek_u64_le, peek_u64_le_opt;
+    u64, from_be_bytes, CoreNumber::U64(Endian::Big), "a `u64` (big endian)" =>
+        read_u64_be, read_u64_be_opt, peek_u64_be, peek_u64_be_opt;
+    u64, from_ne_bytes, CoreNumber::U64(Endian::Native), "a `u64` (native endian)" =>
+        read_u64_ne, read_u64_ne_opt, peek_u64_ne, peek_u64_ne_opt;
+    i64, from_le_bytes, CoreNumber::I64(Endian::Little), "an `i64` (little endian)" =>
+        read_i64_le, read_i64_le_opt, peek_i64_le, peek_i64_le_opt;
+    i64, from_be_bytes, CoreNumber::I64(Endian::Big), "an `i64` (big endian)" =>
+        read_i64_be, read_i64_be_opt, peek_i64_be, peek_i64_be_opt;
+    i64, from_ne_bytes, CoreNumber::I64(Endian::Native), "an `i64` (native endian)" =>
+        read_i64_ne, read_i64_ne_opt, peek_i64_ne, peek_i64_ne_opt;
+    u128, from_le_bytes, CoreNumber::U128(Endian::Little), "a `u128` (little endian)" =>
+        read_u128_le, read_u128_le_opt, peek_u128_le, peek_u128_le_opt;
+    u128, from_be_bytes, CoreNumber::U128(Endian::Big), "a `u128` (big endian)" =>
+        read_u128_be, read_u128_be_opt, peek_u128_be, peek_u128_be_opt;
+    u128, from_ne_bytes, CoreNumber::U128(Endian::Native), "a `u128` (native endian)" =>
+        read_u128_ne, read_u128_ne_opt, peek_u128_ne, peek_u128_ne_opt;
+    i128, from_le_bytes, CoreNumber::I128(Endian::Little), "an `i128` (little endian)" =>
+        read_i128_le, read_i128_le_opt, peek_i128_le, peek_i128_le_opt;
+    i128, from_be_bytes, CoreNumber::I128(Endian::Big), "an `i128` (big endian)" =>
+        read_i128_be, read_i128_be_opt, peek_i128_be, peek_i128_be_opt;
+    i128, from_ne_bytes, CoreNumber::I128(Endian::Native), "an `i128` (native endian)" =>
+        read_i128_ne, read_i128_ne_opt, peek_i128_ne, peek_i128_ne_opt;
+}
+
+macro_rules! impl_read_float {
+    ($(
+        $ty:ident, $from:ident, $num:expr, $desc:literal => $read:ident;
+    )*) => {
+        impl<'i, E> BytesReader<'i, E> {
+            $(
+                #[doc = concat!("Read ", $desc, " accepted by a [`FloatPolicy`].")]
+                ///
+                /// # Example
+                ///
+                /// ```
+                /// use dangerous::{Input, Invalid};
+                /// use dangerous::input::FloatPolicy;
+                ///
+                /// // All bits set is a NaN.
+                #[doc = concat!(
+                    " let input = dangerous::input(&[0xff; core::mem::size_of::<",
+                    stringify!($ty),
+                    ">()]);"
+                )]
+                /// let result: Result<_, Invalid> = input.read_all(|r| {
+                #[doc = concat!("     r.", stringify!($read), "(FloatPolicy::finite())")]
+                /// });
+                ///
+                /// assert!(result.is_err());
+                /// ```
+                ///
+                /// # Errors
+                ///
+                /// Returns [`ExpectedLength`] if there is not enough input to
+                /// read the number and [`ExpectedValid`] if the number was
+                /// rejected by the policy.
+                pub fn $read(&mut self, policy: FloatPolicy) -> Result<$ty, E>
+                where
+                    E: From<ExpectedLength<'i>>,
+                    E: From<ExpectedValid<'i>>,
+                {
+                    let operation = CoreOperation::ReadNum($num);
+                    self.try_advance(|input| {
+                        let (arr, tail) = input
+                            .clone()
+                            .split_array::<E, { mem::size_of::<$ty>() }>(operation)?;
+                        let value = $ty::$from(arr.clone().into_dangerous());
+                        match policy.check(value.classify()) {
+                            Ok(()) => Ok((value, tail)),
+                            Err(expected) => Err(E::from(ExpectedValid {
+                                retry_requirement: None,
+                                context: CoreContext {
+                                    span: arr.span(),
+                                    operation,
+                                    expected: CoreExpected::Valid(expected),
+                                },
+                                input: input.into_maybe_string(),
+                            })),
+                        }
+                    })
+                }
+            )*
+        }
+    };
+}
+
+impl_read_float! {
+    f32, from_le_bytes, CoreNumber::F32(Endian::Little), "an `f32` (little endian)" => read_f32_le;
+    f32, from_be_bytes, CoreNumber::F32(Endian::Big), "an `f32` (big endian)" => read_f32_be;
+    f32, from_ne_bytes, CoreNumber::F32(Endian::Native), "an `f32` (native endian)" => read_f32_ne;
+    f64, from_le_bytes, CoreNumber::F64(Endian::Little), "an `f64` (little endian)" => read_f64_le;
+    f64, from_be_bytes, CoreNumber::F64(Endian::Big), "an `f64` (big endian)" => read_f64_be;
+    f64, from_ne_bytes, CoreNumber::F64(Endian::Native), "an `f64` (native endian)" => read_f64_ne;
+}
+
+macro_rules! impl_read_leb128 {
+    ($(
+        $ty:ident, $bits:literal, $signed:literal, $num:expr, $decode:expr,
+        $desc:literal => $read:ident;
+    )*) => {
+        impl<'i, E> BytesReader<'i, E> {
+            $(
+                #[doc = concat!("Read ", $desc, ".")]
+                ///
+                /// Encodings that are longer than required, or represent a
+                /// value out of range of the integer are rejected.
+                ///
+                /// # Errors
+                ///
+                /// Returns [`ExpectedLength`] if the encoding was cut short
+                /// and [`ExpectedValid`] if the encoding was not canonical or
+                /// out of range.
+                pub fn $read(&mut self) -> Result<$ty, E>
+                where
+                    E: From<ExpectedLength<'i>>,
+                    E: From<ExpectedValid<'i>>,
+                {
+                    self.try_advance(|input| {
+                        input.split_leb128_for($bits, $signed, CoreOperation::ReadNum($num))
+                    })
+                    .map($decode)
+                }
+            )*
+        }
+    };
+}
+
+// The casts below are checked to fit within the width on decoding.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+mod leb128_decode {
+    #[inline(always)]
+    pub(super) fn u32(raw: u64) -> u32 {
+        raw as u32
+    }
+
+    #[inline(always)]
+    pub(super) fn u64(raw: u64) -> u64 {
+        raw
+    }
+
+    #[inline(always)]
+    pub(super) fn i32(raw: u64) -> i32 {
+        raw as i64 as i32
+    }
+
+    #[inline(always)]
+    pub(super) fn i64(raw: u64) -> i64 {
+        raw as i64
+    }
+
+    #[inline(always)]
+    pub(super) fn zigzag_i32(raw: u64) -> i32 {
+        zigzag_i64(raw) as i32
+    }
+
+    #[inline(always)]
+    pub(super) fn zigzag_i64(raw: u64) -> i64 {
+        (raw >> 1) as i64 ^ -((raw & 1) as i64)
+    }
+}
+
+impl_read_leb128! {
+    u32, 32, false, CoreNumber::Uleb128U32, leb128_decode::u32,
+        "an unsigned LEB128 encoded `u32`" => read_uleb128_u32;
+    u64, 64, false, CoreNumber::Uleb128U64, leb128_decode::u64,
+        "an unsigned LEB128 encoded `u64`" => read_uleb128_u64;
+    i32, 32, true, CoreNumber::Sleb128I32, leb128_decode::i32,
+        "a signed LEB128 encoded `i32`" => read_sleb128_i32;
+    i64, 64, true, CoreNumber::Sleb128I64, leb128_decode::i64,
+        "a signed LEB128 encoded `i64`" => read_sleb128_i64;
+    i32, 32, false, CoreNumber::ZigZagI32, leb128_decode::zigzag_i32,
+        "a zigzag LEB128 encoded `i32` (protobuf `sint32`)" => read_zigzag_i32;
+    i64, 64, false, CoreNumber::ZigZagI64, leb128_decode::zigzag_i64,
+        "a zigzag LEB128 encoded `i64` (protobuf `sint64`)" => read_zigzag_i64;
+}
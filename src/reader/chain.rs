@@ -0,0 +1,343 @@
+use core::marker::PhantomData;
+
+use crate::error::{
+    with_context, Context, CoreNumber, CoreOperation, ExpectedLength, ExpectedValue, WithContext,
+};
+use crate::fmt;
+#[cfg(feature = "alloc")]
+use crate::input::Chunk;
+use crate::input::{Chain, Endian};
+
+/// Reads from a [`Chain`] of non-contiguous segments.
+///
+/// Created via [`Chain::read_all()`] or [`Chain::read_partial()`].
+///
+/// Primitives straddling a segment boundary are copied onto the stack, and
+/// [`ChainReader::take()`] only copies input if it straddles a boundary.
+///
+/// Only a subset of the operations of a [`Reader`] are supported: skipping,
+/// taking lengths and arrays, reading and peeking single bytes and numbers,
+/// and consuming exact prefixes. Pattern operations such as `take_while()`
+/// and `take_until()` are not, so input requiring them should be read from
+/// the segment it is within via [`Chain::to_contiguous()`], or copied into a
+/// single buffer first.
+///
+/// Like all readers, the state of the `ChainReader` is left untouched if an
+/// operation fails.
+///
+/// [`Reader`]: crate::Reader
+pub struct ChainReader<'i, E> {
+    input: Chain<'i>,
+    types: PhantomData<E>,
+}
+
+impl<'i, E> ChainReader<'i, E> {
+    pub(crate) fn new(input: Chain<'i>) -> Self {
+        Self {
+            input,
+            types: PhantomData,
+        }
+    }
+
+    /// Consumes the reader, returning the remaining input.
+    pub(crate) fn into_remaining(self) -> Chain<'i> {
+        self.input
+    }
+
+    /// Returns `true` if the `ChainReader` has no more input to consume.
+    #[must_use]
+    #[inline(always)]
+    pub fn at_end(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Returns the number of input bytes left within the reader across all
+    /// segments.
+    ///
+    /// This number is subject to change in future passes for streaming input.
+    #[must_use]
+    pub fn remaining_bytes(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Read all of the remaining input.
+    pub fn take_remaining(&mut self) -> Chain<'i> {
+        let remaining = self.input.clone();
+        // Skipping all of the input always succeeds.
+        if let Some(end) = remaining.clone().split_at_opt(remaining.len()) {
+            self.input = end;
+        }
+        remaining
+    }
+
+    /// Use the `ChainReader` in a mutable context.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the provided function with the specified
+    /// context attached.
+    #[inline(always)]
+    pub fn context<F, T>(&mut self, context: impl Context, f: F) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        with_context(context, self.input.head().clone(), || f(self))
+    }
+
+    /// Skip a length of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length requirement to skip could not be met.
+    pub fn skip(&mut self, len: usize) -> Result<(), E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        match self.input.clone().split_at_opt(len) {
+            Some(next) => {
+                self.input = next;
+                Ok(())
+            }
+            None => Err(self
+                .input
+                .expected_length(len, CoreOperation::Skip, "split")),
+        }
+    }
+
+    /// Skip an optional length of input.
+    ///
+    /// Returns `true` if there was enough input, `false` if not.
+    pub fn skip_opt(&mut self, len: usize) -> bool {
+        match self.input.clone().split_at_opt(len) {
+            Some(next) => {
+                self.input = next;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Read a length of input.
+    ///
+    /// The input is borrowed if it is within a single segment and copied into
+    /// a contiguous buffer otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Invalid;
+    /// use dangerous::input::Chain;
+    ///
+    /// let segments: [&[u8]; 2] = [b"he", b"llo"];
+    /// let result: Result<_, Invalid> = Chain::new(&segments).read_all(|r| {
+    ///     let he = r.take(2)?;
+    ///     let llo = r.take(3)?;
+    ///     Ok((he.is_borrowed(), llo.as_dangerous().to_vec()))
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), (true, b"llo".to_vec()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length requirement to read could not be met.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn take(&mut self, len: usize) -> Result<Chunk<'i>, E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        match self.input.clone().split_chunk_opt(len) {
+            Some((chunk, next)) => {
+                self.input = next;
+                Ok(chunk)
+            }
+            None => Err(self
+                .input
+                .expected_length(len, CoreOperation::Take, "split")),
+        }
+    }
+
+    /// Read an array from input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length requirement to read could not be met.
+    pub fn take_array<const N: usize>(&mut self) -> Result<[u8; N], E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        self.read_array(CoreOperation::TakeArray)
+    }
+
+    /// Read an optional array.
+    ///
+    /// Returns `Some([u8; N])` if there was enough input, `None` if not.
+    pub fn take_array_opt<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let mut array = [0; N];
+        let next = self.input.clone().split_into_opt(&mut array)?;
+        self.input = next;
+        Some(array)
+    }
+
+    /// Read a byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no more input.
+    pub fn read(&mut self) -> Result<u8, E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        self.read_array(CoreOperation::ReadByte).map(|[byte]| byte)
+    }
+
+    /// Read an optional byte.
+    ///
+    /// Returns `Some(u8)` if there was enough input, `None` if not.
+    pub fn read_opt(&mut self) -> Option<u8> {
+        self.take_array_opt().map(|[byte]| byte)
+    }
+
+    /// Peek the next byte in the input without mutating the `ChainReader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `ChainReader` has no more input.
+    pub fn peek_read(&self) -> Result<u8, E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        match self.peek_read_opt() {
+            Some(byte) => Ok(byte),
+            None => Err(self
+                .input
+                .expected_length(1, CoreOperation::PeekByte, "token")),
+        }
+    }
+
+    /// Peek the next byte in the input without mutating the `ChainReader`.
+    ///
+    /// This is equivalent to `peek_read` but does not return an error.
+    #[must_use = "peek result must be used"]
+    pub fn peek_read_opt(&self) -> Option<u8> {
+        self.input.head().as_dangerous().first().copied()
+    }
+
+    /// Consume expected input.
+    ///
+    /// The expected input may straddle segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input could not be consumed.
+    pub fn consume(&mut self, prefix: &'i [u8]) -> Result<(), E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+    {
+        self.input = self
+            .input
+            .clone()
+            .split_prefix::<E>(prefix, CoreOperation::Consume)?;
+        Ok(())
+    }
+
+    /// Consume optional input.
+    ///
+    /// Returns `true` if the input was consumed, `false` if not.
+    ///
+    /// Doesn't effect the internal state of the `ChainReader` if the input
+    /// couldn't be consumed.
+    pub fn consume_opt(&mut self, prefix: &[u8]) -> bool {
+        let mut next = self.input.clone();
+        for &expected in prefix {
+            match next.head().as_dangerous().first() {
+                Some(&byte) if byte == expected => {}
+                _ => return false,
+            }
+            next = match next.split_at_opt(1) {
+                Some(next) => next,
+                None => return false,
+            };
+        }
+        self.input = next;
+        true
+    }
+
+    fn read_array<const N: usize>(&mut self, operation: CoreOperation) -> Result<[u8; N], E>
+    where
+        E: From<ExpectedLength<'i>>,
+    {
+        let mut array = [0; N];
+        match self.input.clone().split_into_opt(&mut array) {
+            Some(next) => {
+                self.input = next;
+                Ok(array)
+            }
+            None => Err(self.input.expected_length(N, operation, "split")),
+        }
+    }
+}
+
+macro_rules! impl_read_num {
+    ($(
+        $ty:ident, $from:ident, $num:expr, $desc:literal => $read:ident;
+    )*) => {
+        impl<'i, E> ChainReader<'i, E> {
+            $(
+                #[doc = concat!("Read ", $desc, ".")]
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if there is not enough input to read the
+                /// number.
+                #[inline]
+                pub fn $read(&mut self) -> Result<$ty, E>
+                where
+                    E: From<ExpectedLength<'i>>,
+                {
+                    self.read_array(CoreOperation::ReadNum($num)).map($ty::$from)
+                }
+            )*
+        }
+    };
+}
+
+impl_read_num! {
+    u8, from_ne_bytes, CoreNumber::U8, "a `u8`" => read_u8;
+    i8, from_ne_bytes, CoreNumber::I8, "an `i8`" => read_i8;
+    u16, from_le_bytes, CoreNumber::U16(Endian::Little), "a `u16` (little endian)" => read_u16_le;
+    u16, from_be_bytes, CoreNumber::U16(Endian::Big), "a `u16` (big endian)" => read_u16_be;
+    u16, from_ne_bytes, CoreNumber::U16(Endian::Native), "a `u16` (native endian)" => read_u16_ne;
+    i16, from_le_bytes, CoreNumber::I16(Endian::Little), "an `i16` (little endian)" => read_i16_le;
+    i16, from_be_bytes, CoreNumber::I16(Endian::Big), "an `i16` (big endian)" => read_i16_be;
+    i16, from_ne_bytes, CoreNumber::I16(Endian::Native), "an `i16` (native endian)" => read_i16_ne;
+    u32, from_le_bytes, CoreNumber::U32(Endian::Little), "a `u32` (little endian)" => read_u32_le;
+    u32, from_be_bytes, CoreNumber::U32(Endian::Big), "a `u32` (big endian)" => read_u32_be;
+    u32, from_ne_bytes, CoreNumber::U32(Endian::Native), "a `u32` (native endian)" => read_u32_ne;
+    i32, from_le_bytes, CoreNumber::I32(Endian::Little), "an `i32` (little endian)" => read_i32_le;
+    i32, from_be_bytes, CoreNumber::I32(Endian::Big), "an `i32` (big endian)" => read_i32_be;
+    i32, from_ne_bytes, CoreNumber::I32(Endian::Native), "an `i32` (native endian)" => read_i32_ne;
+    u64, from_le_bytes, CoreNumber::U64(Endian::Little), "a `u64` (little endian)" => read_u64_le;
+    u64, from_be_bytes, CoreNumber::U64(Endian::Big), "a `u64` (big endian)" => read_u64_be;
+    u64, from_ne_bytes, CoreNumber::U64(Endian::Native), "a `u64` (native endian)" => read_u64_ne;
+    i64, from_le_bytes, CoreNumber::I64(Endian::Little), "an `i64` (little endian)" => read_i64_le;
+    i64, from_be_bytes, CoreNumber::I64(Endian::Big), "an `i64` (big endian)" => read_i64_be;
+    i64, from_ne_bytes, CoreNumber::I64(Endian::Native), "an `i64` (native endian)" => read_i64_ne;
+    u128, from_le_bytes, CoreNumber::U128(Endian::Little), "a `u128` (little endian)" => read_u128_le;
+    u128, from_be_bytes, CoreNumber::U128(Endian::Big), "a `u128` (big endian)" => read_u128_be;
+    u128, from_ne_bytes, CoreNumber::U128(Endian::Native), "a `u128` (native endian)" => read_u128_ne;
+    i128, from_le_bytes, CoreNumber::I128(Endian::Little), "an `i128` (little endian)" => read_i128_le;
+    i128, from_be_bytes, CoreNumber::I128(Endian::Big), "an `i128` (big endian)" => read_i128_be;
+    i128, from_ne_bytes, CoreNumber::I128(Endian::Native), "an `i128` (native endian)" => read_i128_ne;
+}
+
+impl<'i, E> fmt::Debug for ChainReader<'i, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainReader")
+            .field("input", &self.input)
+            .finish()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::input::{Input, Pattern, Prefix, PrivateExt};
+use crate::input::{IgnoreAsciiCase, Input, Pattern, Prefix, PrivateExt, Suffix};
 
 use crate::error::{
     with_context, Context, CoreContext, CoreOperation, ExpectedLength, ExpectedValid,
@@ -499,6 +499,60 @@ where
         })
     }
 
+    /// Read a length of input until the last match of a pattern.
+    ///
+    /// Returns the input leading up to the last pattern match, leaving the
+    /// match and the input after it within the `Reader`.
+    ///
+    /// The end of the remaining input must be bound, as a later match could
+    /// follow otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let input = dangerous::input(b"PK\x03\x04...PK\x05\x06\x01\x00").into_bound();
+    /// let result: Result<_, Invalid> = input.read_all(|r| {
+    ///     let entries = r.take_until_last(b"PK\x05\x06")?;
+    ///     r.consume(b"PK\x05\x06")?;
+    ///     Ok((entries, r.read_u16_le()?))
+    /// });
+    ///
+    /// let (entries, disk) = result.unwrap();
+    ///
+    /// assert_eq!(entries, b"PK\x03\x04..."[..]);
+    /// assert_eq!(disk, 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValue`] if the pattern could not be found and
+    /// [`ExpectedLength`] if the end of the remaining input is not bound.
+    pub fn take_until_last<P>(&mut self, pattern: P) -> Result<I, E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+        P: Pattern<I> + Into<Value<'i>> + Copy,
+    {
+        self.try_advance(|input| input.split_until_last_for(pattern, CoreOperation::TakeUntilLast))
+    }
+
+    /// Read a length of input until the last match of a pattern if any.
+    ///
+    /// Returns the input leading up to the last pattern match, or all of the
+    /// input if the pattern was not found or the end of the remaining input
+    /// is not bound.
+    pub fn take_until_last_opt<P>(&mut self, pattern: P) -> I
+    where
+        P: Pattern<I>,
+    {
+        self.advance(|input| match input.clone().split_until_last_opt(pattern) {
+            Some((taken, next)) => (taken, next),
+            None => (input.clone(), input.end()),
+        })
+    }
+
     /// Read a length of input until a pattern optionally matches.
     ///
     /// If you want to know whether the pattern was consumed or not, check
@@ -647,6 +701,34 @@ where
             .map(drop)
     }
 
+    /// Consume expected input, ignoring ASCII case.
+    ///
+    /// Errors show the expected value as it was provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"HOST: a").read_all(|r| {
+    ///     r.consume_ignore_ascii_case(b"host: ")?;
+    ///     Ok(r.take_remaining())
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"a"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input could not be consumed.
+    pub fn consume_ignore_ascii_case<P>(&mut self, prefix: P) -> Result<(), E>
+    where
+        E: From<ExpectedValue<'i>>,
+        IgnoreAsciiCase<P>: Prefix<I> + Into<Value<'i>>,
+    {
+        self.consume(IgnoreAsciiCase::new(prefix))
+    }
+
     /// Consume optional input.
     ///
     /// Returns `true` if the input was consumed, `false` if not.
@@ -663,6 +745,59 @@ where
         })
     }
 
+    /// Consume expected input from the end of the remaining input.
+    ///
+    /// The end of the remaining input must be bound, as more input could
+    /// follow the suffix otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let input = dangerous::input(b"data\xab\xcd").into_bound();
+    /// let result: Result<_, Invalid> = input.read_all(|r| {
+    ///     r.consume_suffix(b"\xab\xcd")?;
+    ///     Ok(r.take_remaining())
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"data"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValue`] if the input does not end with the suffix and
+    /// [`ExpectedLength`] if the end of the remaining input is not bound.
+    pub fn consume_suffix<P>(&mut self, suffix: P) -> Result<(), E>
+    where
+        E: From<ExpectedValue<'i>>,
+        E: From<ExpectedLength<'i>>,
+        P: Suffix<I> + Into<Value<'i>>,
+    {
+        self.try_advance(|input| {
+            input
+                .split_suffix_for(suffix, CoreOperation::ConsumeSuffix)
+                .map(|(head, _)| ((), head))
+        })
+    }
+
+    /// Consume optional input from the end of the remaining input.
+    ///
+    /// Returns `true` if the input was consumed, `false` if not or the end of
+    /// the remaining input is not bound.
+    ///
+    /// Doesn't effect the internal state of the `Reader` if the input couldn't
+    /// be consumed.
+    pub fn consume_suffix_opt<P>(&mut self, suffix: P) -> bool
+    where
+        P: Suffix<I>,
+    {
+        self.advance(|input| {
+            let (next, suffix) = input.split_suffix_opt(suffix);
+            (suffix.is_some(), next)
+        })
+    }
+
     /// Peek a length of input.
     ///
     /// The function lifetime `'p` helps prevent the peeked [`Input`] being used
@@ -705,6 +840,17 @@ where
         prefix.is_prefix_of(&self.input)
     }
 
+    /// Returns `true` if `prefix` is next in the `Reader`, ignoring ASCII
+    /// case.
+    #[inline]
+    #[must_use = "peek result must be used"]
+    pub fn peek_eq_ignore_ascii_case<P>(&self, prefix: P) -> bool
+    where
+        IgnoreAsciiCase<P>: Prefix<I>,
+    {
+        self.peek_eq(IgnoreAsciiCase::new(prefix))
+    }
+
     /// Peek the next token in the input without mutating the `Reader`.
     ///
     /// # Errors
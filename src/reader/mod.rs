@@ -1,13 +1,21 @@
+mod bits;
 mod bytes;
+mod chain;
 mod input;
 mod peek;
+#[cfg(feature = "regex")]
+mod regex;
+mod repeat;
 
 use core::marker::PhantomData;
 
 use crate::fmt;
 use crate::input::{Bytes, Input, String};
 
+pub use self::bits::BitReader;
+pub use self::chain::ChainReader;
 pub use self::peek::Peek;
+pub use self::repeat::{ManyUntil, Repeat, RepeatN, Separated};
 
 /// [`Bytes`] specific [`Reader`].
 pub type BytesReader<'i, E> = Reader<'i, Bytes<'i>, E>;
@@ -109,6 +117,13 @@ where
         }
     }
 
+    /// Returns the remaining input.
+    #[inline(always)]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    pub(crate) fn remaining(&self) -> I {
+        self.input.clone()
+    }
+
     /// Advances the reader's input given an operation.
     #[inline(always)]
     fn advance<F, O>(&mut self, f: F) -> O
@@ -0,0 +1,146 @@
+use crate::error::{CoreOperation, ExpectedValid};
+#[cfg(feature = "alloc")]
+use crate::input::Captures;
+use crate::input::{Bytes, String};
+
+use super::{BytesReader, StringReader};
+
+impl<'i, E> BytesReader<'i, E> {
+    /// Read a length of input matching a regex at the current position.
+    ///
+    /// Unlike using a regex as a [`Pattern`], the regex must match at the
+    /// start of the remaining input. The regex must be anchored with a leading
+    /// `^` or `\A` (without multi-line mode), so a regex that doesn't match is
+    /// rejected without searching the rest of the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use regex::bytes::Regex;
+    ///
+    /// let regex = Regex::new("^[0-9]+").unwrap();
+    /// let result: Result<_, Invalid> = dangerous::input(b"123;").read_all(|r| {
+    ///     let digits = r.take_regex(&regex)?;
+    ///     r.consume(b';')?;
+    ///     Ok(digits)
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"123"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the regex is not anchored or did not
+    /// match at the current position.
+    ///
+    /// [`Pattern`]: crate::input::Pattern
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn take_regex(&mut self, regex: &regex::bytes::Regex) -> Result<Bytes<'i>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        self.try_advance(|input| input.split_regex_for(regex, CoreOperation::TakeRegex))
+    }
+
+    /// Read a length of input matching a regex at the current position,
+    /// returning the input captured by its groups.
+    ///
+    /// The regex must be anchored with a leading `^` or `\A` (without
+    /// multi-line mode), as with [`take_regex()`](Self::take_regex). The
+    /// captured inputs can be read further with their own readers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use regex::bytes::Regex;
+    ///
+    /// let regex = Regex::new("^(?P<key>[a-z]+)=(?P<value>[0-9]+)").unwrap();
+    /// let result: Result<_, Invalid> = dangerous::input(b"len=10").read_all(|r| {
+    ///     let captures = r.captures(&regex)?;
+    ///     let key = captures.name("key").unwrap();
+    ///     let value = captures.name("value").unwrap();
+    ///     Ok((key, value.read_all::<_, _, Invalid>(|r| r.take_remaining_str())?))
+    /// });
+    ///
+    /// let (key, value) = result.unwrap();
+    /// assert_eq!(key, b"len"[..]);
+    /// assert_eq!(value, "10");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the regex is not anchored or did not
+    /// match at the current position.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "regex", feature = "alloc"))))]
+    pub fn captures<'r>(
+        &mut self,
+        regex: &'r regex::bytes::Regex,
+    ) -> Result<Captures<'r, Bytes<'i>>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        self.try_advance(|input| input.split_captures_for(regex, CoreOperation::TakeCaptures))
+    }
+}
+
+impl<'i, E> StringReader<'i, E> {
+    /// Read a length of input matching a regex at the current position.
+    ///
+    /// Unlike using a regex as a [`Pattern`], the regex must match at the
+    /// start of the remaining input. The regex must be anchored with a leading
+    /// `^` or `\A` (without multi-line mode), so a regex that doesn't match is
+    /// rejected without searching the rest of the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    /// use regex::Regex;
+    ///
+    /// let regex = Regex::new("^[a-z]+").unwrap();
+    /// let result: Result<_, Invalid> = dangerous::input("hello world").read_partial(|r| {
+    ///     r.take_regex(&regex)
+    /// });
+    ///
+    /// let (word, rest) = result.unwrap();
+    /// assert_eq!(word, "hello");
+    /// assert_eq!(rest, " world");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the regex is not anchored or did not
+    /// match at the current position.
+    ///
+    /// [`Pattern`]: crate::input::Pattern
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn take_regex(&mut self, regex: &regex::Regex) -> Result<String<'i>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        self.try_advance(|input| input.split_regex_for(regex, CoreOperation::TakeRegex))
+    }
+
+    /// Read a length of input matching a regex at the current position,
+    /// returning the input captured by its groups.
+    ///
+    /// The regex must be anchored with a leading `^` or `\A` (without
+    /// multi-line mode), as with [`take_regex()`](Self::take_regex). The
+    /// captured inputs can be read further with their own readers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpectedValid`] if the regex is not anchored or did not
+    /// match at the current position.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "regex", feature = "alloc"))))]
+    pub fn captures<'r>(&mut self, regex: &'r regex::Regex) -> Result<Captures<'r, String<'i>>, E>
+    where
+        E: From<ExpectedValid<'i>>,
+    {
+        self.try_advance(|input| input.split_captures_for(regex, CoreOperation::TakeCaptures))
+    }
+}
@@ -0,0 +1,335 @@
+use crate::error::{ExpectedValue, ItemContext, Value, WithContext};
+use crate::fmt;
+use crate::input::{Input, Prefix};
+
+use super::Reader;
+
+impl<'i, I, E> Reader<'i, I, E>
+where
+    I: Input<'i>,
+{
+    /// Repeatedly read items until there is no more input.
+    ///
+    /// Each item is read within an [`ItemContext`] carrying its index. If an
+    /// item is read without consuming any input, the repetition ends after it
+    /// as it would otherwise never finish.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"abc").read_all(|r| {
+    ///     r.repeat(|r| r.read()).collect::<Result<Vec<_>, _>>()
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), b"abc");
+    /// ```
+    ///
+    /// [`ItemContext`]: crate::error::ItemContext
+    pub fn repeat<F, T>(&mut self, f: F) -> Repeat<'_, 'i, I, E, F>
+    where
+        E: WithContext<'i>,
+        F: FnMut(&mut Self) -> Result<T, E>,
+    {
+        Repeat {
+            items: Items::new(self, f),
+        }
+    }
+
+    /// Read exactly `count` items.
+    ///
+    /// Each item is read within an [`ItemContext`] carrying its index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(&[2, 10, 20]).read_all(|r| {
+    ///     let count = r.read()?;
+    ///     r.repeat_n(count.into(), |r| r.read()).collect::<Result<Vec<_>, _>>()
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), &[10, 20]);
+    /// ```
+    ///
+    /// [`ItemContext`]: crate::error::ItemContext
+    pub fn repeat_n<F, T>(&mut self, count: usize, f: F) -> RepeatN<'_, 'i, I, E, F>
+    where
+        E: WithContext<'i>,
+        F: FnMut(&mut Self) -> Result<T, E>,
+    {
+        RepeatN {
+            items: Items::new(self, f),
+            count,
+        }
+    }
+
+    /// Read one or more items, separated by a `separator` prefix.
+    ///
+    /// The repetition ends when an item is not followed by the separator,
+    /// which is consumed between each item.
+    ///
+    /// Each item is read within an [`ItemContext`] carrying its index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"a,b,c;").read_partial(|r| {
+    ///     r.separated(b',', |r| r.read()).collect::<Result<Vec<_>, _>>()
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), (b"abc".to_vec(), dangerous::input(b";")));
+    /// ```
+    ///
+    /// [`ItemContext`]: crate::error::ItemContext
+    pub fn separated<F, T, P>(&mut self, separator: P, f: F) -> Separated<'_, 'i, I, E, F, P>
+    where
+        E: WithContext<'i>,
+        F: FnMut(&mut Self) -> Result<T, E>,
+        P: Prefix<I> + Copy,
+    {
+        Separated {
+            items: Items::new(self, f),
+            separator,
+        }
+    }
+
+    /// Repeatedly read items until a `terminator` prefix is found and
+    /// consumed.
+    ///
+    /// Each item is read within an [`ItemContext`] carrying its index. If an
+    /// item is read without consuming any input, the terminator must follow
+    /// it as the repetition would otherwise never finish.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::{Input, Invalid};
+    ///
+    /// let result: Result<_, Invalid> = dangerous::input(b"ab;c").read_partial(|r| {
+    ///     r.many_until(b';', |r| r.read()).collect::<Result<Vec<_>, _>>()
+    /// });
+    ///
+    /// assert_eq!(result.unwrap(), (b"ab".to_vec(), dangerous::input(b"c")));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The iterator yields [`ExpectedValue`] if the input ends, or an item
+    /// consumes no input, before the terminator is found.
+    ///
+    /// [`ItemContext`]: crate::error::ItemContext
+    pub fn many_until<F, T, P>(&mut self, terminator: P, f: F) -> ManyUntil<'_, 'i, I, E, F, P>
+    where
+        E: WithContext<'i>,
+        E: From<ExpectedValue<'i>>,
+        F: FnMut(&mut Self) -> Result<T, E>,
+        P: Prefix<I> + Into<Value<'i>> + Copy,
+    {
+        ManyUntil {
+            items: Items::new(self, f),
+            terminator,
+            stalled: false,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+struct Items<'r, 'i, I, E, F> {
+    reader: &'r mut Reader<'i, I, E>,
+    f: F,
+    index: usize,
+    done: bool,
+}
+
+impl<'r, 'i, I, E, F> Items<'r, 'i, I, E, F>
+where
+    I: Input<'i>,
+{
+    fn new(reader: &'r mut Reader<'i, I, E>, f: F) -> Self {
+        Self {
+            reader,
+            f,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Reads the next item, finishing the repetition on error.
+    fn read<T>(&mut self) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+    {
+        let context = ItemContext::new(self.reader.input.span(), self.index);
+        let result = self.reader.context(context, &mut self.f);
+        self.index += 1;
+        self.done |= result.is_err();
+        result
+    }
+
+    /// Reads the next item, finishing the repetition on error or if no input
+    /// was consumed.
+    fn read_progressing<T>(&mut self) -> Result<T, E>
+    where
+        E: WithContext<'i>,
+        F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+    {
+        let before = self.reader.remaining_bytes();
+        let result = self.read();
+        self.done |= self.reader.remaining_bytes() == before;
+        result
+    }
+}
+
+/// Iterator returned by [`Reader::repeat()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Repeat<'r, 'i, I, E, F> {
+    items: Items<'r, 'i, I, E, F>,
+}
+
+impl<'r, 'i, I, E, F, T> Iterator for Repeat<'r, 'i, I, E, F>
+where
+    I: Input<'i>,
+    E: WithContext<'i>,
+    F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.items.done || self.items.reader.at_end() {
+            None
+        } else {
+            Some(self.items.read_progressing())
+        }
+    }
+}
+
+/// Iterator returned by [`Reader::repeat_n()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RepeatN<'r, 'i, I, E, F> {
+    items: Items<'r, 'i, I, E, F>,
+    count: usize,
+}
+
+impl<'r, 'i, I, E, F, T> Iterator for RepeatN<'r, 'i, I, E, F>
+where
+    I: Input<'i>,
+    E: WithContext<'i>,
+    F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.items.done || self.items.index == self.count {
+            None
+        } else {
+            Some(self.items.read())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.items.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.count - self.items.index))
+        }
+    }
+}
+
+/// Iterator returned by [`Reader::separated()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Separated<'r, 'i, I, E, F, P> {
+    items: Items<'r, 'i, I, E, F>,
+    separator: P,
+}
+
+impl<'r, 'i, I, E, F, P, T> Iterator for Separated<'r, 'i, I, E, F, P>
+where
+    I: Input<'i>,
+    E: WithContext<'i>,
+    F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+    P: Prefix<I> + Copy,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.items.done {
+            return None;
+        }
+        if self.items.index > 0 && !self.items.reader.consume_opt(self.separator) {
+            self.items.done = true;
+            return None;
+        }
+        Some(self.items.read())
+    }
+}
+
+/// Iterator returned by [`Reader::many_until()`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ManyUntil<'r, 'i, I, E, F, P> {
+    items: Items<'r, 'i, I, E, F>,
+    terminator: P,
+    /// Whether the last item was read without consuming any input.
+    stalled: bool,
+}
+
+impl<'r, 'i, I, E, F, P, T> Iterator for ManyUntil<'r, 'i, I, E, F, P>
+where
+    I: Input<'i>,
+    E: WithContext<'i>,
+    E: From<ExpectedValue<'i>>,
+    F: FnMut(&mut Reader<'i, I, E>) -> Result<T, E>,
+    P: Prefix<I> + Into<Value<'i>> + Copy,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.items.done {
+            None
+        } else if self.items.reader.consume_opt(self.terminator) {
+            self.items.done = true;
+            None
+        } else if self.stalled || self.items.reader.at_end() {
+            self.items.done = true;
+            // The terminator could not be consumed, so this returns the error.
+            match self.items.reader.consume(self.terminator) {
+                Ok(()) => None,
+                Err(err) => Some(Err(err)),
+            }
+        } else {
+            let before = self.items.reader.remaining_bytes();
+            let result = self.items.read();
+            self.stalled = self.items.reader.remaining_bytes() == before;
+            Some(result)
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Formatting
+
+macro_rules! impl_debug {
+    ($($ty:ident<$($param:ident),*>),*) => {
+        $(
+            impl<'r, 'i, I, E, $($param),*> fmt::Debug for $ty<'r, 'i, I, E, $($param),*>
+            where
+                I: Input<'i>,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_struct(stringify!($ty))
+                        .field("reader", &self.items.reader)
+                        .field("index", &self.items.index)
+                        .finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_debug!(Repeat<F>, RepeatN<F>, Separated<F, P>, ManyUntil<F, P>);
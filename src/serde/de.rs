@@ -0,0 +1,474 @@
+use serde::de::{self, DeserializeSeed, Unexpected, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::error::{with_context, Context, ItemContext};
+use crate::fmt;
+use crate::input::Input;
+use crate::BytesReader;
+
+use super::error::Error;
+use super::read::{self, Kind, Number, Str};
+
+const MAX_DEPTH: usize = 128;
+
+/// A `serde` [`Deserializer`](de::Deserializer) for a strict subset of JSON
+/// reading through a [`BytesReader`].
+///
+/// See the [module documentation](super) for details.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub struct Deserializer<'r, 'i, E> {
+    reader: &'r mut BytesReader<'i, E>,
+    depth: usize,
+}
+
+impl<'r, 'i, E> Deserializer<'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    /// Create a new `Deserializer` given a [`BytesReader`].
+    pub fn new(reader: &'r mut BytesReader<'i, E>) -> Self {
+        Self {
+            reader,
+            depth: MAX_DEPTH,
+        }
+    }
+
+    /// Reads a value within a context, converting any message raised while
+    /// deserializing to the reader's error.
+    pub(super) fn read_value<F, T>(&mut self, context: impl Context, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error<E>>,
+    {
+        read::skip_whitespace(self.reader);
+        let depth = self.depth;
+        let start = self.reader.remaining();
+        with_context(context, start.clone(), || {
+            let result = f(self);
+            self.depth = depth;
+            result.map_err(|err| err.into_input_error(&start, self.reader))
+        })
+    }
+
+    fn value<F, T>(&mut self, context: impl Context, f: F) -> Result<T, Error<E>>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error<E>>,
+    {
+        self.read_value(context, f).map_err(Error::from)
+    }
+
+    fn enter(&mut self) -> Result<(), E> {
+        self.reader
+            .verify("json value within depth limit", |_| self.depth > 0)?;
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn peek_kind(&mut self) -> Result<Kind, E> {
+        read::skip_whitespace(self.reader);
+        read::peek_kind(self.reader)
+    }
+
+    fn visit_seq<V>(&mut self, visitor: V) -> Result<V::Value, Error<E>>
+    where
+        V: Visitor<'i>,
+    {
+        self.enter()?;
+        self.reader.consume(b'[')?;
+        let mut access = SeqAccess {
+            de: self,
+            index: 0,
+            end: false,
+        };
+        let value = visitor.visit_seq(&mut access)?;
+        if !access.end {
+            read::skip_whitespace(access.de.reader);
+            access.de.reader.consume(b']')?;
+        }
+        Ok(value)
+    }
+
+    fn visit_map<V>(
+        &mut self,
+        visitor: V,
+        fields: &'static [&'static str],
+    ) -> Result<V::Value, Error<E>>
+    where
+        V: Visitor<'i>,
+    {
+        self.enter()?;
+        self.reader.consume(b'{')?;
+        let mut access = MapAccess {
+            de: self,
+            fields,
+            field: None,
+            index: 0,
+            end: false,
+        };
+        let value = visitor.visit_map(&mut access)?;
+        if !access.end {
+            read::skip_whitespace(access.de.reader);
+            access.de.reader.consume(b'}')?;
+        }
+        Ok(value)
+    }
+}
+
+impl<'a, 'r, 'i, E> de::Deserializer<'i> for &'a mut Deserializer<'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        match self.peek_kind()? {
+            Kind::Null => self.value("json null", |de| {
+                read::read_null(de.reader)?;
+                visitor.visit_unit()
+            }),
+            Kind::Bool => self.value("json boolean", |de| {
+                visitor.visit_bool(read::read_bool(de.reader)?)
+            }),
+            Kind::Number => self.value("json number", |de| match read::read_number(de.reader)? {
+                Number::U64(value) => visitor.visit_u64(value),
+                Number::I64(value) => visitor.visit_i64(value),
+                Number::F64(value) => visitor.visit_f64(value),
+            }),
+            Kind::String => self.value("json string", |de| match read::read_str(de.reader)? {
+                Str::Borrowed(value) => visitor.visit_borrowed_str(value),
+                Str::Owned(value) => visitor.visit_str(&value),
+            }),
+            Kind::Array => self.deserialize_seq(visitor),
+            Kind::Object => self.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        match self.peek_kind()? {
+            Kind::String => self.value("json string", |de| match read::read_str(de.reader)? {
+                Str::Borrowed(value) => visitor.visit_borrowed_bytes(value.as_bytes()),
+                Str::Owned(value) => visitor.visit_bytes(value.as_bytes()),
+            }),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        match self.peek_kind()? {
+            Kind::Null => self.value("json null", |de| {
+                read::read_null(de.reader)?;
+                visitor.visit_none()
+            }),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.value("json array", |de| de.visit_seq(visitor))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.value("json object", |de| de.visit_map(visitor, &[]))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.value(name, |de| de.visit_map(visitor, fields))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.value(name, |de| {
+            if let Kind::String = de.peek_kind()? {
+                return visitor.visit_enum(UnitVariantAccess { de });
+            }
+            de.enter()?;
+            de.reader.consume(b'{')?;
+            let value = visitor.visit_enum(VariantAccess { de: &mut *de })?;
+            read::skip_whitespace(de.reader);
+            de.reader.consume(b'}')?;
+            Ok(value)
+        })
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'i>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Arrays
+
+struct SeqAccess<'a, 'r, 'i, E> {
+    de: &'a mut Deserializer<'r, 'i, E>,
+    index: usize,
+    /// Whether the closing bracket was consumed.
+    end: bool,
+}
+
+impl<'a, 'r, 'i, E> de::SeqAccess<'i> for SeqAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'i>,
+    {
+        read::skip_whitespace(self.de.reader);
+        if self.de.reader.consume_opt(b']') {
+            self.end = true;
+            return Ok(None);
+        }
+        if self.index > 0 {
+            self.de.reader.consume(b',')?;
+            read::skip_whitespace(self.de.reader);
+        }
+        let context = ItemContext::new(self.de.reader.remaining().span(), self.index);
+        self.index += 1;
+        self.de.value(context, |de| seed.deserialize(de)).map(Some)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Objects
+
+struct MapAccess<'a, 'r, 'i, E> {
+    de: &'a mut Deserializer<'r, 'i, E>,
+    fields: &'static [&'static str],
+    field: Option<&'static str>,
+    index: usize,
+    /// Whether the closing brace was consumed.
+    end: bool,
+}
+
+impl<'a, 'r, 'i, E> de::MapAccess<'i> for MapAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'i>,
+    {
+        read::skip_whitespace(self.de.reader);
+        if self.de.reader.consume_opt(b'}') {
+            self.end = true;
+            return Ok(None);
+        }
+        if self.index > 0 {
+            self.de.reader.consume(b',')?;
+        }
+        let fields = self.fields;
+        let (field, key) = self.de.value("json object key", |de| {
+            let key = read::read_str(de.reader)?;
+            let field = fields.iter().copied().find(|&field| field == key.as_str());
+            let key = match key {
+                Str::Borrowed(key) => {
+                    seed.deserialize(de::value::BorrowedStrDeserializer::<Error<E>>::new(key))
+                }
+                Str::Owned(key) => {
+                    seed.deserialize(de::value::StrDeserializer::<Error<E>>::new(&key))
+                }
+            }?;
+            Ok((field, key))
+        })?;
+        self.field = field;
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'i>,
+    {
+        read::skip_whitespace(self.de.reader);
+        self.de.reader.consume(b':')?;
+        read::skip_whitespace(self.de.reader);
+        let index = self.index;
+        self.index += 1;
+        if let Some(field) = self.field.take() {
+            return self.de.value(field, |de| seed.deserialize(de));
+        }
+        let context = ItemContext::new(self.de.reader.remaining().span(), index);
+        self.de.value(context, |de| seed.deserialize(de))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Enums
+
+/// Access for an enum variant written as a string.
+struct UnitVariantAccess<'a, 'r, 'i, E> {
+    de: &'a mut Deserializer<'r, 'i, E>,
+}
+
+impl<'a, 'r, 'i, E> de::EnumAccess<'i> for UnitVariantAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Self::Error>
+    where
+        V: DeserializeSeed<'i>,
+    {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'a, 'r, 'i, E> de::VariantAccess<'i> for UnitVariantAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'i>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+/// Access for an enum variant written as an object with a single member.
+struct VariantAccess<'a, 'r, 'i, E> {
+    de: &'a mut Deserializer<'r, 'i, E>,
+}
+
+impl<'a, 'r, 'i, E> de::EnumAccess<'i> for VariantAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Self::Error>
+    where
+        V: DeserializeSeed<'i>,
+    {
+        let variant = seed.deserialize(&mut *self.de)?;
+        read::skip_whitespace(self.de.reader);
+        self.de.reader.consume(b':')?;
+        Ok((variant, self))
+    }
+}
+
+impl<'a, 'r, 'i, E> de::VariantAccess<'i> for VariantAccess<'a, 'r, 'i, E>
+where
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    type Error = Error<E>;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'i>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'i>,
+    {
+        self.de
+            .value("json object", |de| de.visit_map(visitor, fields))
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::string::{String, ToString};
+use core::any::Any;
+
+use serde::de;
+
+use crate::error::{Context, CoreOperation, External, Operation, WithContext};
+use crate::fmt;
+use crate::input::{Bytes, Input, PrivateExt, Span};
+use crate::BytesReader;
+
+/// Error returned from the [`Deserializer`](super::Deserializer).
+///
+/// Wraps either the error returned from reading input, or a message raised by
+/// a `Deserialize` implementation. Messages are converted to the reader's
+/// error with the span of the value being deserialized before leaving the
+/// `Deserializer`.
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub struct Error<E>(ErrorInner<E>);
+
+#[derive(Debug)]
+enum ErrorInner<E> {
+    Input(E),
+    Message(String),
+}
+
+impl<E> Error<E> {
+    /// Converts the error into the reader's error type given the input before
+    /// the value started being deserialized.
+    pub(super) fn into_input_error<'i>(self, start: &Bytes<'i>, r: &BytesReader<'i, E>) -> E
+    where
+        E: WithContext<'i>,
+        E: From<crate::error::ExpectedValid<'i>>,
+    {
+        match self.0 {
+            ErrorInner::Input(error) => error,
+            ErrorInner::Message(message) => {
+                let consumed = start.byte_len() - r.remaining_bytes();
+                let value = match start.clone().split_at_opt(consumed) {
+                    Some((value, _)) => value,
+                    None => start.clone(),
+                };
+                let message = Message {
+                    span: value.span(),
+                    message,
+                };
+                value.map_external_error(
+                    message,
+                    "deserializable value",
+                    CoreOperation::ExpectExternal,
+                )
+            }
+        }
+    }
+}
+
+impl<E> From<E> for Error<E> {
+    #[inline(always)]
+    fn from(error: E) -> Self {
+        Self(ErrorInner::Input(error))
+    }
+}
+
+impl<E> fmt::Display for Error<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ErrorInner::Input(error) => error.fmt(f),
+            ErrorInner::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl<E> de::StdError for Error<E> where E: fmt::Debug + fmt::Display {}
+
+impl<E> de::Error for Error<E>
+where
+    E: fmt::Debug + fmt::Display,
+{
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self(ErrorInner::Message(msg.to_string()))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Message context
+
+/// A message raised by a `Deserialize` implementation.
+struct Message {
+    span: Span,
+    message: String,
+}
+
+impl<'i> External<'i> for Message {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn push_backtrace<E>(self, error: E) -> E
+    where
+        E: WithContext<'i>,
+    {
+        error.with_context(self)
+    }
+}
+
+impl Context for Message {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn operation(&self) -> &dyn Operation {
+        self
+    }
+}
+
+impl Operation for Message {
+    fn description(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str(&self.message)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,99 @@
+//! `serde` support.
+//!
+//! Provides a [`Deserializer`] for a strict subset of JSON ([RFC 8259]) that
+//! reads through a [`BytesReader`], so every failure is returned as your
+//! chosen error (such as [`Expected`]) with a context backtrace and span.
+//!
+//! - Strings without escapes are borrowed from the input, so `&'i str` and
+//!   `&'i [u8]` fields are zero-copy.
+//! - Object keys must be strings, and whitespace, trailing commas or comments
+//!   outside of what JSON allows are rejected.
+//! - Arrays and objects may only be nested up to a depth of `128`.
+//!
+//! Errors raised by `Deserialize` implementations (such as a missing field) are
+//! converted to an [`ExpectedValid`] spanning the value that was being
+//! deserialized, with the message attached as a context.
+//!
+//! # Example
+//!
+//! ```
+//! use dangerous::Expected;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct User<'a> {
+//!     name: &'a str,
+//!     age: u8,
+//! }
+//!
+//! let input = dangerous::input(br#"{ "name": "bob", "age": 42 }"#);
+//! let user: User<'_> = dangerous::serde::from_input::<_, Expected<'_>>(input).unwrap();
+//!
+//! assert_eq!(user.name, "bob");
+//! assert_eq!(user.age, 42);
+//! ```
+//!
+//! [RFC 8259]: https://www.rfc-editor.org/rfc/rfc8259
+//! [`Expected`]: crate::Expected
+//! [`ExpectedValid`]: crate::error::ExpectedValid
+
+mod de;
+mod error;
+mod read;
+
+use serde::Deserialize;
+
+use crate::fmt;
+use crate::input::Input;
+use crate::BytesReader;
+
+pub use self::de::Deserializer;
+pub use self::error::Error;
+
+/// Deserialize a value of type `T` from all of the provided input.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid JSON value, if `T` failed to
+/// deserialize from it, or if there is trailing input.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub fn from_input<'i, T, E>(input: impl Input<'i>) -> Result<T, E>
+where
+    T: Deserialize<'i>,
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    input.into_bytes().read_all(read)
+}
+
+/// Deserialize a value of type `T` from a [`BytesReader`].
+///
+/// Any whitespace surrounding the value is skipped.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::{Expected, Input};
+///
+/// let input = dangerous::input(b"[1, 2] rest");
+/// let (values, rest): (Vec<u32>, _) = input
+///     .read_partial::<_, _, Expected<'_>>(|r| dangerous::serde::read(r))
+///     .unwrap();
+///
+/// assert_eq!(values, [1, 2]);
+/// assert_eq!(rest, b"rest"[..]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a valid JSON value or if
+/// `T` failed to deserialize from it.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "alloc"))))]
+pub fn read<'i, T, E>(r: &mut BytesReader<'i, E>) -> Result<T, E>
+where
+    T: Deserialize<'i>,
+    E: crate::Error<'i> + fmt::Debug + fmt::Display,
+{
+    let value = Deserializer::new(r).read_value("json value", |de| T::deserialize(de))?;
+    read::skip_whitespace(r);
+    Ok(value)
+}
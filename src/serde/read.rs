@@ -0,0 +1,214 @@
+use alloc::string::String;
+
+use crate::input::{Bytes, Input};
+use crate::{BytesReader, Error};
+
+/// The kind of JSON value next in the input.
+#[derive(Copy, Clone)]
+pub(super) enum Kind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// A JSON string, borrowed from the input if it contains no escapes.
+pub(super) enum Str<'i> {
+    Borrowed(&'i str),
+    Owned(String),
+}
+
+impl<'i> Str<'i> {
+    pub(super) fn as_str(&self) -> &str {
+        match self {
+            Self::Borrowed(s) => s,
+            Self::Owned(s) => s,
+        }
+    }
+}
+
+/// A JSON number.
+#[derive(Copy, Clone)]
+pub(super) enum Number {
+    U64(u64),
+    I64(i64),
+    F64(f64),
+}
+
+pub(super) fn skip_whitespace<E>(r: &mut BytesReader<'_, E>) {
+    r.skip_while(|c: u8| matches!(c, b' ' | b'\t' | b'\n' | b'\r'));
+}
+
+pub(super) fn peek_kind<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Kind, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("json value", |r| {
+        let kind = match r.peek_read()? {
+            b'n' => Kind::Null,
+            b't' | b'f' => Kind::Bool,
+            b'-' | b'0'..=b'9' => Kind::Number,
+            b'"' => Kind::String,
+            b'[' => Kind::Array,
+            b'{' => Kind::Object,
+            _ => return Ok(None),
+        };
+        Ok(Some(kind))
+    })
+}
+
+pub(super) fn read_null<'i, E>(r: &mut BytesReader<'i, E>) -> Result<(), E>
+where
+    E: Error<'i>,
+{
+    r.consume(b"null")
+}
+
+pub(super) fn read_bool<'i, E>(r: &mut BytesReader<'i, E>) -> Result<bool, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("json boolean", |r| match r.peek_read()? {
+        b't' => r.consume(b"true").map(|()| Some(true)),
+        b'f' => r.consume(b"false").map(|()| Some(false)),
+        _ => Ok(None),
+    })
+}
+
+pub(super) fn read_number<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Number, E>
+where
+    E: Error<'i>,
+{
+    let (is_float, text) = r.try_take_consumed(|r| {
+        let mut is_float = false;
+        r.consume_opt(b'-');
+        if !r.consume_opt(b'0') {
+            r.try_verify("non-zero digit", |r| {
+                r.read().map(|c| matches!(c, b'1'..=b'9'))
+            })?;
+            r.skip_while(|c: u8| c.is_ascii_digit());
+        }
+        if r.consume_opt(b'.') {
+            is_float = true;
+            read_digits(r)?;
+        }
+        if r.consume_opt(b'e') || r.consume_opt(b'E') {
+            is_float = true;
+            if !r.consume_opt(b'+') {
+                r.consume_opt(b'-');
+            }
+            read_digits(r)?;
+        }
+        Ok(is_float)
+    })?;
+    let text = text.into_string::<E>()?;
+    if !is_float {
+        if let Ok(value) = text.as_dangerous().parse() {
+            return Ok(Number::U64(value));
+        }
+        if let Ok(value) = text.as_dangerous().parse() {
+            return Ok(Number::I64(value));
+        }
+    }
+    text.into_external("f64", |i| i.as_dangerous().parse().map(Number::F64))
+}
+
+fn read_digits<'i, E>(r: &mut BytesReader<'i, E>) -> Result<(), E>
+where
+    E: Error<'i>,
+{
+    r.try_verify("digit", |r| r.read().map(|c| c.is_ascii_digit()))?;
+    r.skip_while(|c: u8| c.is_ascii_digit());
+    Ok(())
+}
+
+pub(super) fn read_str<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Str<'i>, E>
+where
+    E: Error<'i>,
+{
+    r.consume(b'"')?;
+    let head = take_unescaped(r).to_dangerous_str::<E>()?;
+    if r.consume_opt(b'"') {
+        return Ok(Str::Borrowed(head));
+    }
+    let mut owned = String::from(head);
+    loop {
+        let escaped = r.try_expect("json string character", |r| match r.read()? {
+            b'"' => Ok(Some(false)),
+            b'\\' => Ok(Some(true)),
+            _ => Ok(None),
+        })?;
+        if !escaped {
+            return Ok(Str::Owned(owned));
+        }
+        owned.push(read_escape(r)?);
+        owned.push_str(take_unescaped(r).to_dangerous_str::<E>()?);
+    }
+}
+
+fn take_unescaped<'i, E>(r: &mut BytesReader<'i, E>) -> Bytes<'i> {
+    r.take_while(|c: u8| c != b'"' && c != b'\\' && c >= 0x20)
+}
+
+fn read_escape<'i, E>(r: &mut BytesReader<'i, E>) -> Result<char, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("json escape", |r| {
+        let c = match r.read()? {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => return read_unicode_escape(r).map(Some),
+            _ => return Ok(None),
+        };
+        Ok(Some(c))
+    })
+}
+
+fn read_unicode_escape<'i, E>(r: &mut BytesReader<'i, E>) -> Result<char, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("unicode escape", |r| {
+        let high = read_hex4(r)?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                r.consume(b"\\u")?;
+                match read_hex4(r)? {
+                    low @ 0xDC00..=0xDFFF => {
+                        0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(low) - 0xDC00)
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            _ => u32::from(high),
+        };
+        Ok(char::from_u32(code))
+    })
+}
+
+fn read_hex4<'i, E>(r: &mut BytesReader<'i, E>) -> Result<u16, E>
+where
+    E: Error<'i>,
+{
+    r.try_expect("4 hex digits", |r| {
+        let digits = r.take_array::<4>()?;
+        Ok(digits.as_dangerous().iter().try_fold(0, |value, &c| {
+            let digit = match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => return None,
+            };
+            Some(value << 4 | u16::from(digit))
+        }))
+    })
+}
@@ -0,0 +1,134 @@
+use std::io;
+use std::string::ToString;
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::Decoder;
+
+use crate::error::ToRetryRequirement;
+use crate::fmt;
+use crate::input::{Bound, Bytes, Input};
+use crate::reader::BytesReader;
+use crate::Expected;
+
+use super::{StreamError, DEFAULT_LIMIT, READ_STEP};
+
+/// A [`tokio_util::codec::Decoder`] decoding frames with a function.
+///
+/// The decoding function is called with a reader over the bytes received so
+/// far, like [`Input::read_partial()`], and the bytes it consumed are removed
+/// once a frame is decoded.
+///
+/// - If it fails with an error that has a [`RetryRequirement`], `Ok(None)` is
+///   returned with capacity reserved for the input required, at most 8 KiB
+///   at a time so input claiming a huge length can't reserve it up front.
+/// - If it fails with an error that can't be retried, the error is converted
+///   into an [`InvalidData`](io::ErrorKind::InvalidData) [`io::Error`]
+///   carrying its rendered [`ErrorDisplay`].
+///
+/// Errors are [`Expected`] so they can be rendered in full, which is done
+/// before the input they borrow is released.
+///
+/// Once the stream has ended, the remaining bytes are decoded as bound input
+/// so an incomplete frame fails with an error describing what was missing.
+///
+/// # Example
+///
+/// ```
+/// use bytes::BytesMut;
+/// use dangerous::input::LengthPrefix;
+/// use dangerous::stream::Codec;
+/// use tokio_util::codec::Decoder;
+///
+/// let mut codec = Codec::new(|r| {
+///     let body = r.take_length_prefixed(LengthPrefix::U8)?;
+///     Ok(body.as_dangerous().to_vec())
+/// });
+/// let mut src = BytesMut::from(&b"\x05he"[..]);
+///
+/// assert_eq!(codec.decode(&mut src).unwrap(), None);
+/// src.extend_from_slice(b"llo");
+/// assert_eq!(codec.decode(&mut src).unwrap(), Some(b"hello".to_vec()));
+/// ```
+///
+/// [`Input::read_partial()`]: crate::Input::read_partial()
+/// [`RetryRequirement`]: crate::error::RetryRequirement
+/// [`ErrorDisplay`]: crate::display::ErrorDisplay
+pub struct Codec<F> {
+    f: F,
+    limit: usize,
+}
+
+impl<F, T> Codec<F>
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, Expected<'i>>) -> Result<T, Expected<'i>>,
+{
+    /// Creates a new `Codec` decoding frames with the provided function.
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// Sets the maximum length of input the codec will buffer for a frame.
+    ///
+    /// Decoding fails if a frame requires more input than this. Defaults to
+    /// 8 MiB.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn decode_bound(&mut self, src: &mut BytesMut, bound: Bound) -> Result<Option<T>, io::Error> {
+        match Bytes::new(src, bound).read_partial(&mut self.f) {
+            Ok((value, remaining)) => {
+                let consumed = src.len() - remaining.len();
+                src.advance(consumed);
+                Ok(Some(value))
+            }
+            Err(err) => match err.to_retry_requirement() {
+                Some(req) => {
+                    let required = src.len().saturating_add(req.continue_after());
+                    if required > self.limit {
+                        let err = StreamError::<Expected<'_>>::LimitExceeded { required };
+                        return Err(err.into());
+                    }
+                    src.reserve(req.continue_after().min(READ_STEP));
+                    Ok(None)
+                }
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    err.display().to_string(),
+                )),
+            },
+        }
+    }
+}
+
+impl<F, T> Decoder for Codec<F>
+where
+    F: for<'i> FnMut(&mut BytesReader<'i, Expected<'i>>) -> Result<T, Expected<'i>>,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        self.decode_bound(src, Bound::Start)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        self.decode_bound(src, Bound::force_close())
+    }
+}
+
+impl<F> fmt::Debug for Codec<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Codec")
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,438 @@
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io;
+
+use futures_core::Stream;
+use futures_io::{AsyncBufRead, AsyncRead};
+
+use crate::error::Error;
+use crate::fmt;
+use crate::reader::BytesReader;
+
+use super::{Buffer, Step, StreamError};
+
+/// Decodes values from a [`futures_io::AsyncRead`] stream.
+///
+/// The asynchronous counterpart to [`StreamDecoder`]. The retry requirement
+/// of a failed decode is kept between polls, so the decoding function is
+/// only called again once as many bytes as it required have been read, no
+/// matter how many partial reads that takes.
+///
+/// Reads are sized to exactly what is required, so many small reads may be
+/// made from the underlying source. For an [`AsyncBufRead`] stream, use
+/// [`poll_decode_buf()`](AsyncStreamDecoder::poll_decode_buf) or
+/// [`into_buf_stream()`](AsyncStreamDecoder::into_buf_stream) instead to
+/// decode straight from the stream's own buffer.
+///
+/// [`StreamDecoder`]: super::StreamDecoder
+/// [`AsyncBufRead`]: futures_io::AsyncBufRead
+pub struct AsyncStreamDecoder<R> {
+    inner: R,
+    buf: Buffer,
+}
+
+impl<R> AsyncStreamDecoder<R> {
+    /// Creates a new `AsyncStreamDecoder` reading from a stream.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(0, inner)
+    }
+
+    /// Creates a new `AsyncStreamDecoder` with an initial buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self {
+            inner,
+            buf: Buffer::with_capacity(capacity),
+        }
+    }
+
+    /// Sets the maximum length of input the decoder will buffer.
+    ///
+    /// Decoding fails with [`StreamError::LimitExceeded`] if a value requires
+    /// more input than this. Defaults to 8 MiB.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.buf.limit = limit;
+        self
+    }
+
+    /// Returns the input read from the stream that has not yet been decoded.
+    #[must_use]
+    pub fn buffer(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+
+    /// Returns `true` if the stream has reached its end.
+    #[must_use]
+    pub fn is_eof(&self) -> bool {
+        self.buf.eof
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Reading from the stream directly will skip input the decoder expects.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the decoder, returning the underlying stream.
+    ///
+    /// Any input buffered but not yet decoded is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Converts the decoder into a [`Stream`] of the values decoded with the
+    /// provided function.
+    ///
+    /// The stream ends once the underlying stream has no input left to
+    /// decode, or after the first error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Invalid;
+    /// use dangerous::input::LengthPrefix;
+    /// use dangerous::stream::AsyncStreamDecoder;
+    /// use futures::{executor, StreamExt};
+    ///
+    /// let stream: &[u8] = b"\x02hi\x05hello";
+    /// let bodies = AsyncStreamDecoder::new(stream).into_stream(|r| {
+    ///     let body = r.take_length_prefixed(LengthPrefix::U8)?;
+    ///     Ok::<_, Invalid>(body.as_dangerous().to_vec())
+    /// });
+    /// let bodies = executor::block_on(bodies.collect::<Vec<_>>());
+    ///
+    /// assert_eq!(bodies.len(), 2);
+    /// assert_eq!(bodies[1].as_ref().unwrap(), b"hello");
+    /// ```
+    pub fn into_stream<F, T, E>(self, f: F) -> DecodeStream<R, F, E>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        DecodeStream {
+            decoder: self,
+            f,
+            done: false,
+            error: PhantomData,
+        }
+    }
+
+    /// Converts the decoder into a [`Stream`] of the values decoded with the
+    /// provided function from an [`AsyncBufRead`] stream.
+    ///
+    /// Values are decoded with
+    /// [`poll_decode_buf()`](AsyncStreamDecoder::poll_decode_buf). The stream
+    /// ends once the underlying stream has no input left to decode, or after
+    /// the first error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dangerous::Invalid;
+    /// use dangerous::input::LengthPrefix;
+    /// use dangerous::stream::AsyncStreamDecoder;
+    /// use futures::{executor, StreamExt};
+    ///
+    /// let stream: &[u8] = b"\x02hi\x05hello";
+    /// let bodies = AsyncStreamDecoder::new(stream).into_buf_stream(|r| {
+    ///     let body = r.take_length_prefixed(LengthPrefix::U8)?;
+    ///     Ok::<_, Invalid>(body.as_dangerous().to_vec())
+    /// });
+    /// let bodies = executor::block_on(bodies.collect::<Vec<_>>());
+    ///
+    /// assert_eq!(bodies.len(), 2);
+    /// assert_eq!(bodies[1].as_ref().unwrap(), b"hello");
+    /// ```
+    pub fn into_buf_stream<F, T, E>(self, f: F) -> DecodeBufStream<R, F, E>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        DecodeBufStream {
+            decoder: self,
+            f,
+            done: false,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<R> AsyncStreamDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Attempts to decode a value from the stream.
+    ///
+    /// The provided function is called with a reader over the buffered input,
+    /// like [`Input::read_partial()`]. If it fails with an error that has a
+    /// [`RetryRequirement`], it is only called again once as many more bytes
+    /// as required have been read from the stream.
+    ///
+    /// Once the stream has reached its end the buffered input is bound, so
+    /// the function fails with an error that can't be retried if it requires
+    /// more input.
+    ///
+    /// Returns `Poll::Ready(Ok(None))` if the stream reached its end with no
+    /// input left to decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the stream fails, the function fails
+    /// with an error that can't be retried, or more input is required than the
+    /// [`limit`](AsyncStreamDecoder::limit) allows.
+    ///
+    /// [`Input::read_partial()`]: crate::Input::read_partial()
+    /// [`RetryRequirement`]: crate::error::RetryRequirement
+    pub fn poll_decode<F, T, E>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut f: F,
+    ) -> Poll<Result<Option<T>, StreamError<E>>>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        loop {
+            if self.buf.is_ready() {
+                match self.buf.decode(&mut f) {
+                    Ok(Step::Done(value)) => return Poll::Ready(Ok(value)),
+                    Ok(Step::Fill) => {}
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+            let spare = match self.buf.spare_mut() {
+                Ok(spare) => spare,
+                Err(err) => return Poll::Ready(Err(StreamError::Io(err))),
+            };
+            match Pin::new(&mut self.inner).poll_read(cx, spare) {
+                Poll::Ready(Ok(len)) => self.buf.advance(len),
+                Poll::Ready(Err(err)) if err.kind() == io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(StreamError::Io(err))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<R> AsyncStreamDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Attempts to decode a value from a buffered stream.
+    ///
+    /// Like [`poll_decode()`](AsyncStreamDecoder::poll_decode), but while the
+    /// decoder has no input buffered the function is called directly with the
+    /// input in the stream's buffer, so values within it are decoded without
+    /// being copied. Input is only copied into the decoder's buffer once a
+    /// value requires more than the stream has buffered.
+    ///
+    /// Returns `Poll::Ready(Ok(None))` if the stream reached its end with no
+    /// input left to decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the stream fails, the buffer can't be
+    /// grown, the function fails with an error that can't be retried, or more
+    /// input is required than the [`limit`](AsyncStreamDecoder::limit) allows.
+    pub fn poll_decode_buf<F, T, E>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut f: F,
+    ) -> Poll<Result<Option<T>, StreamError<E>>>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        loop {
+            let borrow = self.buf.is_ready() && self.buf.is_empty() && !self.buf.eof;
+            if self.buf.is_ready() && !borrow {
+                match self.buf.decode(&mut f) {
+                    Ok(Step::Done(value)) => return Poll::Ready(Ok(value)),
+                    Ok(Step::Fill) => {}
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+            let available = match Pin::new(&mut self.inner).poll_fill_buf(cx) {
+                Poll::Ready(Ok(available)) => available,
+                Poll::Ready(Err(err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(StreamError::Io(err))),
+                Poll::Pending => return Poll::Pending,
+            };
+            if available.is_empty() {
+                self.buf.advance(0);
+                continue;
+            }
+            let (step, consumed) = if borrow {
+                match self.buf.decode_borrowed(available, &mut f) {
+                    Ok(result) => result,
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            } else {
+                let spare = match self.buf.spare_mut() {
+                    Ok(spare) => spare,
+                    Err(err) => return Poll::Ready(Err(StreamError::Io(err))),
+                };
+                let len = spare.len().min(available.len());
+                spare[..len].copy_from_slice(&available[..len]);
+                self.buf.advance(len);
+                (Step::Fill, len)
+            };
+            Pin::new(&mut self.inner).consume(consumed);
+            if let Step::Done(value) = step {
+                return Poll::Ready(Ok(value));
+            }
+        }
+    }
+}
+
+impl<R> fmt::Debug for AsyncStreamDecoder<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncStreamDecoder")
+            .field("inner", &self.inner)
+            .field("buf", &self.buf)
+            .finish()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// DecodeStream
+
+/// A [`Stream`] of values decoded from an [`AsyncStreamDecoder`].
+///
+/// Created via [`AsyncStreamDecoder::into_stream()`].
+#[must_use = "streams do nothing unless polled"]
+pub struct DecodeStream<R, F, E> {
+    decoder: AsyncStreamDecoder<R>,
+    f: F,
+    done: bool,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<R, F, E> DecodeStream<R, F, E> {
+    /// Returns a reference to the decoder.
+    pub fn get_ref(&self) -> &AsyncStreamDecoder<R> {
+        &self.decoder
+    }
+
+    /// Consumes the stream, returning the decoder.
+    pub fn into_inner(self) -> AsyncStreamDecoder<R> {
+        self.decoder
+    }
+}
+
+impl<R, F, T, E> Stream for DecodeStream<R, F, E>
+where
+    R: AsyncRead + Unpin,
+    F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E> + Unpin,
+    E: for<'i> Error<'i>,
+{
+    type Item = Result<T, StreamError<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let poll = this.decoder.poll_decode(cx, &mut this.f);
+        poll_next_value(&mut this.done, poll)
+    }
+}
+
+impl<R, F, E> fmt::Debug for DecodeStream<R, F, E>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeStream")
+            .field("decoder", &self.decoder)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// DecodeBufStream
+
+/// A [`Stream`] of values decoded from an [`AsyncStreamDecoder`] over an
+/// [`AsyncBufRead`] stream.
+///
+/// Created via [`AsyncStreamDecoder::into_buf_stream()`].
+#[must_use = "streams do nothing unless polled"]
+pub struct DecodeBufStream<R, F, E> {
+    decoder: AsyncStreamDecoder<R>,
+    f: F,
+    done: bool,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<R, F, E> DecodeBufStream<R, F, E> {
+    /// Returns a reference to the decoder.
+    pub fn get_ref(&self) -> &AsyncStreamDecoder<R> {
+        &self.decoder
+    }
+
+    /// Consumes the stream, returning the decoder.
+    pub fn into_inner(self) -> AsyncStreamDecoder<R> {
+        self.decoder
+    }
+}
+
+impl<R, F, T, E> Stream for DecodeBufStream<R, F, E>
+where
+    R: AsyncBufRead + Unpin,
+    F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E> + Unpin,
+    E: for<'i> Error<'i>,
+{
+    type Item = Result<T, StreamError<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let poll = this.decoder.poll_decode_buf(cx, &mut this.f);
+        poll_next_value(&mut this.done, poll)
+    }
+}
+
+impl<R, F, E> fmt::Debug for DecodeBufStream<R, F, E>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeBufStream")
+            .field("decoder", &self.decoder)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Maps a decode poll to the next item of a stream, marking the stream done
+/// at the end of the input or after the first error.
+fn poll_next_value<T, E>(
+    done: &mut bool,
+    poll: Poll<Result<Option<T>, StreamError<E>>>,
+) -> Poll<Option<Result<T, StreamError<E>>>> {
+    match poll {
+        Poll::Ready(Ok(Some(value))) => Poll::Ready(Some(Ok(value))),
+        Poll::Ready(Ok(None)) => {
+            *done = true;
+            Poll::Ready(None)
+        }
+        Poll::Ready(Err(err)) => {
+            *done = true;
+            Poll::Ready(Some(Err(err)))
+        }
+        Poll::Pending => Poll::Pending,
+    }
+}
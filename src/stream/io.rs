@@ -0,0 +1,160 @@
+use std::io;
+
+use crate::error::Error;
+use crate::fmt;
+use crate::reader::BytesReader;
+
+use super::{Buffer, Step, StreamError};
+
+/// Decodes values from a [`std::io::Read`] stream.
+///
+/// The decoder owns a growable buffer of the input read so far. Each call to
+/// [`StreamDecoder::decode()`] reads only as many bytes as the decoding
+/// function reports it requires via its [`RetryRequirement`], so no input
+/// following the decoded value is read from the stream. The buffer grows with
+/// the bytes actually read rather than with the length required, so input
+/// claiming a huge length can't allocate it up front.
+///
+/// Input consumed by a decoded value is removed from the buffer before the
+/// next value is decoded.
+///
+/// # Example
+///
+/// ```
+/// use dangerous::Invalid;
+/// use dangerous::input::LengthPrefix;
+/// use dangerous::stream::StreamDecoder;
+///
+/// let stream: &[u8] = b"\x02hi\x05hello";
+/// let mut decoder = StreamDecoder::new(stream);
+/// let mut next = || {
+///     decoder.decode::<_, _, Invalid>(|r| {
+///         let body = r.take_length_prefixed(LengthPrefix::U8)?;
+///         Ok(body.as_dangerous().to_vec())
+///     })
+/// };
+///
+/// assert_eq!(next().unwrap(), Some(b"hi".to_vec()));
+/// assert_eq!(next().unwrap(), Some(b"hello".to_vec()));
+/// assert_eq!(next().unwrap(), None);
+/// ```
+///
+/// [`RetryRequirement`]: crate::error::RetryRequirement
+pub struct StreamDecoder<R> {
+    inner: R,
+    buf: Buffer,
+}
+
+impl<R> StreamDecoder<R> {
+    /// Creates a new `StreamDecoder` reading from a stream.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(0, inner)
+    }
+
+    /// Creates a new `StreamDecoder` with an initial buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self {
+            inner,
+            buf: Buffer::with_capacity(capacity),
+        }
+    }
+
+    /// Sets the maximum length of input the decoder will buffer.
+    ///
+    /// Decoding fails with [`StreamError::LimitExceeded`] if a value requires
+    /// more input than this. Defaults to 8 MiB.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.buf.limit = limit;
+        self
+    }
+
+    /// Returns the input read from the stream that has not yet been decoded.
+    #[must_use]
+    pub fn buffer(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+
+    /// Returns `true` if the stream has reached its end.
+    #[must_use]
+    pub fn is_eof(&self) -> bool {
+        self.buf.eof
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Reading from the stream directly will skip input the decoder expects.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the decoder, returning the underlying stream.
+    ///
+    /// Any input buffered but not yet decoded is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> StreamDecoder<R>
+where
+    R: io::Read,
+{
+    /// Decodes a value from the stream.
+    ///
+    /// The provided function is called with a reader over the buffered input,
+    /// like [`Input::read_partial()`]. If it fails with an error that has a
+    /// [`RetryRequirement`], exactly as many more bytes as required are read
+    /// from the stream before it is called again.
+    ///
+    /// Once the stream has reached its end the buffered input is bound, so
+    /// the function fails with an error that can't be retried if it requires
+    /// more input.
+    ///
+    /// Returns `Ok(None)` if the stream reached its end with no input left to
+    /// decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the stream fails, the buffer can't be
+    /// grown, the function fails with an error that can't be retried, or more
+    /// input is required than the [`limit`](StreamDecoder::limit) allows.
+    ///
+    /// [`Input::read_partial()`]: crate::Input::read_partial()
+    /// [`RetryRequirement`]: crate::error::RetryRequirement
+    pub fn decode<F, T, E>(&mut self, mut f: F) -> Result<Option<T>, StreamError<E>>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        loop {
+            if self.buf.is_ready() {
+                if let Step::Done(value) = self.buf.decode(&mut f)? {
+                    return Ok(value);
+                }
+            }
+            match self.inner.read(self.buf.spare_mut()?) {
+                Ok(len) => self.buf.advance(len),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(StreamError::Io(err)),
+            }
+        }
+    }
+}
+
+impl<R> fmt::Debug for StreamDecoder<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamDecoder")
+            .field("inner", &self.inner)
+            .field("buf", &self.buf)
+            .finish()
+    }
+}
@@ -0,0 +1,317 @@
+//! Incremental decoding of input read from a stream.
+//!
+//! Input read from a stream may arrive in any number of pieces. A
+//! [`StreamDecoder`] buffers what has been read so far and retries your
+//! decoding function once it has read as many more bytes as the
+//! [`RetryRequirement`] of the last failure asked for. With the `futures`
+//! feature enabled, an `AsyncStreamDecoder` does the same for
+//! `futures_io::AsyncRead` and `AsyncBufRead` streams, and with the `tokio`
+//! feature enabled a `Codec` adapts a decoding function to a
+//! `tokio_util::codec::Decoder`.
+//!
+//! Input from a stream is untrusted, so decoders buffer at most 8 MiB for a
+//! value by default and only grow their buffer as input is actually read. See
+//! [`StreamDecoder::limit()`] to change the limit.
+//!
+//! The decoding function is called for every retry, so it must be callable
+//! with input of any lifetime. Values decoded must be owned and errors must
+//! not borrow the input, such as [`Invalid`] or [`Fatal`]. If you need a
+//! detailed error, decode [`StreamDecoder::buffer()`] again with [`Expected`]
+//! once the decoding has failed. A `Codec` instead decodes with [`Expected`]
+//! errors, rendering them before the input they borrow is released.
+//!
+//! [`RetryRequirement`]: crate::error::RetryRequirement
+//! [`Invalid`]: crate::Invalid
+//! [`Fatal`]: crate::Fatal
+//! [`Expected`]: crate::Expected
+
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "futures")]
+mod futures;
+mod io;
+
+use std::vec::Vec;
+
+use crate::error::Error;
+use crate::fmt;
+use crate::input::{Bound, Bytes, Input};
+use crate::reader::BytesReader;
+
+#[cfg(feature = "tokio")]
+pub use self::codec::Codec;
+#[cfg(feature = "futures")]
+pub use self::futures::{AsyncStreamDecoder, DecodeBufStream, DecodeStream};
+pub use self::io::StreamDecoder;
+
+/// An error returned while decoding from a stream.
+#[derive(Debug)]
+pub enum StreamError<E> {
+    /// Reading from the stream failed.
+    Io(std::io::Error),
+    /// The input could not be decoded.
+    Decode(E),
+    /// More input was required than the decoder is allowed to buffer.
+    LimitExceeded {
+        /// The length of buffer that was required.
+        required: usize,
+    },
+}
+
+impl<E> fmt::Display for StreamError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read from stream: {}", err),
+            Self::Decode(err) => err.fmt(f),
+            Self::LimitExceeded { required } => write!(
+                f,
+                "buffer limit exceeded: {} bytes were required to decode",
+                required
+            ),
+        }
+    }
+}
+
+impl<E> std::error::Error for StreamError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::LimitExceeded { .. } => None,
+        }
+    }
+}
+
+impl<E> From<std::io::Error> for StreamError<E> {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Converts the error into an [`std::io::Error`].
+///
+/// Errors that didn't come from reading the stream are converted to an
+/// [`InvalidData`](std::io::ErrorKind::InvalidData) error carrying the
+/// rendered display of the error.
+impl<E> From<StreamError<E>> for std::io::Error
+where
+    E: fmt::Display,
+{
+    fn from(err: StreamError<E>) -> Self {
+        match err {
+            StreamError::Io(err) => err,
+            err => Self::new(std::io::ErrorKind::InvalidData, err.to_string()),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Buffer
+
+/// The default maximum length of input buffered for a value, matching the
+/// default frame length of tokio's `LengthDelimitedCodec`.
+pub(crate) const DEFAULT_LIMIT: usize = 8 * 1024 * 1024;
+
+/// The most the buffer is grown by before the bytes are actually read, so a
+/// value claiming to require a huge length can't allocate it up front.
+const READ_STEP: usize = 8 * 1024;
+
+/// The outcome of trying to decode the buffered input.
+enum Step<T> {
+    /// Decoding finished with a value, or `None` at the end of the stream.
+    Done(Option<T>),
+    /// The buffer must be filled before decoding is tried again.
+    Fill,
+}
+
+/// Input read from a stream that has not yet been decoded.
+///
+/// The bytes of `buf` from `start` to `end` are the buffered input, with the
+/// bytes after `end` free to be read into.
+struct Buffer {
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    /// The number of bytes still to be read before decoding is tried again.
+    wanted: usize,
+    limit: usize,
+    eof: bool,
+}
+
+impl Buffer {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+            start: 0,
+            end: 0,
+            wanted: 0,
+            limit: DEFAULT_LIMIT,
+            eof: false,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    /// Returns `true` if decoding should be tried with the buffered input.
+    fn is_ready(&self) -> bool {
+        self.wanted == 0
+    }
+
+    /// Returns `true` if no input is buffered.
+    #[cfg(feature = "futures")]
+    fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Tries to decode a value from the buffered input.
+    ///
+    /// Input decoded by a previous value is removed first. If decoding fails
+    /// with a retry requirement, the buffer is marked as wanting that many
+    /// more bytes.
+    fn decode<F, T, E>(&mut self, f: &mut F) -> Result<Step<T>, StreamError<E>>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        self.compact();
+        if self.eof && self.start == self.end {
+            return Ok(Step::Done(None));
+        }
+        let bound = if self.eof {
+            Bound::force_close()
+        } else {
+            Bound::Start
+        };
+        match Bytes::new(self.as_slice(), bound).read_partial(f) {
+            Ok((value, remaining)) => {
+                self.start = self.end - remaining.len();
+                Ok(Step::Done(Some(value)))
+            }
+            Err(err) => {
+                self.wanted = self.retry(err, self.end)?;
+                Ok(Step::Fill)
+            }
+        }
+    }
+
+    /// Tries to decode a value from input borrowed from a buffered stream
+    /// while no input is buffered here, returning the length of the input
+    /// consumed from the stream.
+    ///
+    /// If decoding fails with a retry requirement, the input is copied into
+    /// the buffer, which is marked as wanting that many more bytes.
+    #[cfg(feature = "futures")]
+    fn decode_borrowed<F, T, E>(
+        &mut self,
+        input: &[u8],
+        f: &mut F,
+    ) -> Result<(Step<T>, usize), StreamError<E>>
+    where
+        F: for<'i> FnMut(&mut BytesReader<'i, E>) -> Result<T, E>,
+        E: for<'i> Error<'i>,
+    {
+        debug_assert!(self.is_empty() && !self.eof);
+        match Bytes::new(input, Bound::Start).read_partial(f) {
+            Ok((value, remaining)) => Ok((Step::Done(Some(value)), input.len() - remaining.len())),
+            Err(err) => {
+                let wanted = self.retry(err, input.len())?;
+                self.compact();
+                self.extend(input)?;
+                self.wanted = wanted;
+                Ok((Step::Fill, input.len()))
+            }
+        }
+    }
+
+    /// Returns the number of bytes wanted after decoding `len` bytes failed
+    /// with the error, if it can be retried within the limit.
+    fn retry<E>(&self, err: E, len: usize) -> Result<usize, StreamError<E>>
+    where
+        E: for<'i> Error<'i>,
+    {
+        match err.to_retry_requirement() {
+            Some(req) if !self.eof => {
+                let required = len.saturating_add(req.continue_after());
+                if required > self.limit {
+                    return Err(StreamError::LimitExceeded { required });
+                }
+                Ok(req.continue_after())
+            }
+            _ => Err(StreamError::Decode(err)),
+        }
+    }
+
+    /// Appends input to the buffer.
+    #[cfg(feature = "futures")]
+    fn extend(&mut self, input: &[u8]) -> std::io::Result<()> {
+        self.buf.truncate(self.end);
+        self.buf
+            .try_reserve(input.len())
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+        self.buf.extend_from_slice(input);
+        self.end += input.len();
+        Ok(())
+    }
+
+    /// Returns the space the next of the wanted bytes should be read into.
+    ///
+    /// The space is at most [`READ_STEP`] bytes, so the buffer only grows
+    /// with the input actually read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OutOfMemory`](std::io::ErrorKind::OutOfMemory) error if
+    /// the buffer could not be grown.
+    fn spare_mut(&mut self) -> std::io::Result<&mut [u8]> {
+        let required = self.end + self.wanted.min(READ_STEP);
+        if self.buf.len() < required {
+            self.buf
+                .try_reserve(required - self.buf.len())
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+            self.buf.resize(required, 0);
+        }
+        Ok(&mut self.buf[self.end..required])
+    }
+
+    /// Marks `len` bytes read into the spare space as buffered input.
+    ///
+    /// Reading zero bytes marks the end of the stream.
+    fn advance(&mut self, len: usize) {
+        debug_assert!(len <= self.wanted);
+        if len == 0 {
+            self.eof = true;
+            self.wanted = 0;
+        } else {
+            self.end += len;
+            self.wanted -= len;
+        }
+    }
+
+    /// Moves the buffered input to the start of the buffer.
+    fn compact(&mut self) {
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+    }
+}
+
+impl fmt::Debug for Buffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Buffer")
+            .field("input", &Bytes::new(self.as_slice(), Bound::Start))
+            .field("wanted", &self.wanted)
+            .field("limit", &self.limit)
+            .field("eof", &self.eof)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::input::{Bound, Bytes, IntoInput, Parent, Span};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<'i> IntoInput<'i> for &'i bytes::Bytes {
+    type Input = Bytes<'i>;
+
+    #[inline(always)]
+    fn into_input(self) -> Self::Input {
+        Bytes::new(self, Bound::Start)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<'i> IntoInput<'i> for &'i bytes::BytesMut {
+    type Input = Bytes<'i>;
+
+    #[inline(always)]
+    fn into_input(self) -> Self::Input {
+        Bytes::new(self, Bound::Start)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl Parent for bytes::Bytes {
+    #[inline]
+    fn extract(self, span: Span) -> Option<Self> {
+        span.range_of(Span::from(self.as_ref()))
+            .map(|range| self.slice(range))
+    }
+}
@@ -1,6 +1,10 @@
+#[cfg(feature = "bytes")]
+mod bytes;
 mod core;
 #[cfg(feature = "nom")]
 mod nom;
+#[cfg(feature = "serde")]
+mod serde;
 #[cfg(feature = "std")]
 mod std;
 #[cfg(feature = "zc")]
@@ -0,0 +1,39 @@
+use core::ops::Range;
+
+use serde::de::{DeserializeSeed, Deserializer, Error as _, Unexpected};
+use serde::ser::{Error as _, Serialize, Serializer};
+use serde::Deserialize;
+
+use crate::input::{Span, SpanIn, SpanSeed};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for SpanIn {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.span.range_of(self.parent) {
+            Some(range) => range.serialize(serializer),
+            None => Err(S::Error::custom("span is not within the parent")),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> DeserializeSeed<'de> for SpanSeed {
+    type Value = Span;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let range = Range::<usize>::deserialize(deserializer)?;
+        match self.parent.sub_span(range) {
+            Some(span) => Ok(span),
+            None => Err(D::Error::invalid_value(
+                Unexpected::Other("span offsets"),
+                &"offsets within the parent",
+            )),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use crate::input::ByteSet;
 use crate::util::utf8::CharBytes;
 
+/// The length of the blocks compared at once when searching for a reject.
+const REJECT_BLOCK_LEN: usize = 64;
+
 ///////////////////////////////////////////////////////////////////////////////
 // u8
 
@@ -25,9 +29,70 @@ pub(crate) fn find_u8_match(needle: u8, haystack: &[u8]) -> Option<usize> {
     haystack.iter().copied().position(|b| b == needle)
 }
 
-// FIXME: impl SIMD variant
+#[cfg(feature = "memchr")]
+#[inline(always)]
+pub(crate) fn find_u8_match_rev(needle: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memrchr(needle, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_u8_match_rev(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().copied().rposition(|b| b == needle)
+}
+
 pub(crate) fn find_u8_reject(needle: u8, haystack: &[u8]) -> Option<usize> {
-    haystack.iter().copied().position(|b| b != needle)
+    let mut offset = 0;
+    // Folding the differences of a whole block without an early exit allows
+    // the compiler to vectorise the comparison.
+    for block in haystack.chunks_exact(REJECT_BLOCK_LEN) {
+        if block.iter().fold(0, |diff, b| diff | (b ^ needle)) != 0 {
+            break;
+        }
+        offset += REJECT_BLOCK_LEN;
+    }
+    haystack[offset..]
+        .iter()
+        .position(|b| *b != needle)
+        .map(|i| offset + i)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// byte set
+
+#[cfg(feature = "memchr")]
+pub(crate) fn find_set_match(set: ByteSet<'_>, haystack: &[u8]) -> Option<usize> {
+    match *set.as_bytes() {
+        [] => None,
+        [a] => memchr::memchr(a, haystack),
+        [a, b] => memchr::memchr2(a, b, haystack),
+        [a, b, c] => memchr::memchr3(a, b, c, haystack),
+        _ => haystack.iter().position(|b| set.contains(*b)),
+    }
+}
+
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_set_match(set: ByteSet<'_>, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| set.contains(*b))
+}
+
+#[cfg(feature = "memchr")]
+pub(crate) fn find_set_match_rev(set: ByteSet<'_>, haystack: &[u8]) -> Option<usize> {
+    match *set.as_bytes() {
+        [] => None,
+        [a] => memchr::memrchr(a, haystack),
+        [a, b] => memchr::memrchr2(a, b, haystack),
+        [a, b, c] => memchr::memrchr3(a, b, c, haystack),
+        _ => haystack.iter().rposition(|b| set.contains(*b)),
+    }
+}
+
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_set_match_rev(set: ByteSet<'_>, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().rposition(|b| set.contains(*b))
+}
+
+pub(crate) fn find_set_reject(set: ByteSet<'_>, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| !set.contains(*b))
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -45,18 +110,60 @@ pub(crate) fn num_chars(s: &str) -> usize {
     s.chars().count()
 }
 
+/// Returns the index and char of the first char `f` returns `true` for.
+///
+/// ASCII chars are passed to `f` straight from their byte, only decoding
+/// the multi-byte chars.
+#[inline(always)]
+pub(crate) fn find_char_by<F>(haystack: &str, mut f: F) -> Option<(usize, char)>
+where
+    F: FnMut(char) -> bool,
+{
+    let mut offset = 0;
+    loop {
+        let rest = haystack.get(offset..)?;
+        // Scan the run of ASCII chars until a match or a multi-byte char.
+        let i = rest
+            .as_bytes()
+            .iter()
+            .position(|&b| !b.is_ascii() || f(b as char))?;
+        offset += i;
+        // `offset` is always advanced by whole chars so is a char boundary.
+        let c = haystack.get(offset..)?.chars().next()?;
+        if c.is_ascii() || f(c) {
+            return Some((offset, c));
+        }
+        offset += c.len_utf8();
+    }
+}
+
 #[inline(always)]
 pub(crate) fn find_char_match(needle: char, haystack: &[u8]) -> Option<usize> {
     let needle = CharBytes::from(needle);
     find_slice_match(needle.as_bytes(), haystack)
 }
 
+#[inline(always)]
+pub(crate) fn find_char_match_rev(needle: char, haystack: &[u8]) -> Option<usize> {
+    let needle = CharBytes::from(needle);
+    find_slice_match_rev(needle.as_bytes(), haystack)
+}
+
 #[inline(always)]
 pub(crate) fn find_char_reject(needle: char, haystack: &[u8]) -> Option<usize> {
     let needle = CharBytes::from(needle);
     find_slice_reject(needle.as_bytes(), haystack)
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// utf-8
+
+#[cfg(feature = "simdutf8")]
+pub(crate) use simdutf8::compat::from_utf8;
+
+#[cfg(not(feature = "simdutf8"))]
+pub(crate) use core::str::from_utf8;
+
 ///////////////////////////////////////////////////////////////////////////////
 // slice
 
@@ -82,17 +189,98 @@ pub(crate) fn find_slice_match(needle: &[u8], haystack: &[u8]) -> Option<usize>
         .find_map(|(i, w)| if w == needle { Some(i) } else { None })
 }
 
-// FIXME: impl SIMD variant
+#[cfg(feature = "memchr")]
+pub(crate) fn find_slice_match_rev(needle: &[u8], haystack: &[u8]) -> Option<usize> {
+    if haystack.is_empty() || needle.is_empty() {
+        return None;
+    }
+    match needle.len() {
+        1 => memchr::memrchr(needle[0], haystack),
+        _ => memchr::memmem::rfind(haystack, needle),
+    }
+}
+
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_slice_match_rev(needle: &[u8], haystack: &[u8]) -> Option<usize> {
+    if haystack.is_empty() || needle.is_empty() {
+        return None;
+    }
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
 pub(crate) fn find_slice_reject(needle: &[u8], haystack: &[u8]) -> Option<usize> {
     if haystack.is_empty() || needle.is_empty() || haystack.len() < needle.len() {
         return Some(0);
     }
-    haystack
+    if needle.len() == 1 {
+        return find_u8_reject(needle[0], haystack);
+    }
+    // Repeat the needle to fill a block so whole blocks can be compared at
+    // once, before finding the exact chunk rejected.
+    let mut block = [0; REJECT_BLOCK_LEN];
+    let block_len = REJECT_BLOCK_LEN - REJECT_BLOCK_LEN % needle.len();
+    for chunk in block[..block_len].chunks_exact_mut(needle.len()) {
+        chunk.copy_from_slice(needle);
+    }
+    let mut offset = 0;
+    if block_len > 0 {
+        for chunk in haystack.chunks_exact(block_len) {
+            if chunk != &block[..block_len] {
+                break;
+            }
+            offset += block_len;
+        }
+    }
+    haystack[offset..]
         .chunks(needle.len())
         .enumerate()
         .find_map(|(i, w)| {
             if w == needle {
                 None
+            } else {
+                Some(offset + i * needle.len())
+            }
+        })
+}
+
+pub(crate) fn find_slice_match_ignore_ascii_case(needle: &[u8], haystack: &[u8]) -> Option<usize> {
+    if haystack.is_empty() || needle.is_empty() {
+        return None;
+    }
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .find_map(|(i, w)| {
+            if w.eq_ignore_ascii_case(needle) {
+                Some(i)
+            } else {
+                None
+            }
+        })
+}
+
+pub(crate) fn find_slice_match_rev_ignore_ascii_case(
+    needle: &[u8],
+    haystack: &[u8],
+) -> Option<usize> {
+    if haystack.is_empty() || needle.is_empty() {
+        return None;
+    }
+    haystack
+        .windows(needle.len())
+        .rposition(|w| w.eq_ignore_ascii_case(needle))
+}
+
+pub(crate) fn find_slice_reject_ignore_ascii_case(needle: &[u8], haystack: &[u8]) -> Option<usize> {
+    if haystack.is_empty() || needle.is_empty() || haystack.len() < needle.len() {
+        return Some(0);
+    }
+    haystack
+        .chunks(needle.len())
+        .enumerate()
+        .find_map(|(i, w)| {
+            if w.eq_ignore_ascii_case(needle) {
+                None
             } else {
                 Some(i * needle.len())
             }
@@ -0,0 +1,84 @@
+//! Line and column support shared by error displays and
+//! [`LineIndex`](crate::input::LineIndex).
+//!
+//! A line ends at a `\n`, with any `\r` directly before it excluded from the
+//! line, and columns are counted with each invalid UTF-8 byte as one char.
+
+use core::iter;
+
+use crate::util::{fast, utf8::CharIter};
+
+pub(crate) struct Line {
+    /// One-based line number.
+    pub(crate) number: usize,
+    /// Byte offset of the start of the line.
+    pub(crate) start: usize,
+    /// Byte offset of the end of the line, not including the line ending.
+    pub(crate) end: usize,
+}
+
+/// Returns an iterator over the lines of the input.
+pub(crate) fn lines(input: &[u8]) -> impl Iterator<Item = Line> + '_ {
+    let mut next = Some(0);
+    let mut number = 0;
+    iter::from_fn(move || {
+        let start = next?;
+        let (end, after) = line_end(input, start);
+        number += 1;
+        next = after;
+        Some(Line { number, start, end })
+    })
+}
+
+/// Returns the byte offset of the end of the line starting at `start`, not
+/// including the line ending, and the start of the next line if any.
+pub(crate) fn line_end(input: &[u8], start: usize) -> (usize, Option<usize>) {
+    match fast::find_u8_match(b'\n', &input[start..]) {
+        Some(index) => {
+            let end = if index > 0 && input[start + index - 1] == b'\r' {
+                start + index - 1
+            } else {
+                start + index
+            };
+            (end, Some(start + index + 1))
+        }
+        None => (input.len(), None),
+    }
+}
+
+/// Returns the one-based line number and byte offset of the start of the line
+/// containing the byte offset.
+pub(crate) fn line_containing(input: &[u8], offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let number = fast::count_u8(b'\n', before) + 1;
+    let start = fast::find_u8_match_rev(b'\n', before).map_or(0, |i| i + 1);
+    (number, start)
+}
+
+/// Returns the number of lines within the input, which is always at least
+/// one.
+pub(crate) fn line_count(input: &[u8]) -> usize {
+    fast::count_u8(b'\n', input) + 1
+}
+
+/// Returns the number of chars, counting each invalid UTF-8 byte as one.
+pub(crate) fn char_count(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    for_each_char(bytes, |_| count += 1);
+    count
+}
+
+/// Calls `f` for each char, with each invalid UTF-8 byte replaced with
+/// [`char::REPLACEMENT_CHARACTER`].
+pub(crate) fn for_each_char(mut bytes: &[u8], mut f: impl FnMut(char)) {
+    while !bytes.is_empty() {
+        let mut iter = CharIter::new(bytes);
+        if let Some(Ok(c)) = iter.next() {
+            f(c);
+            bytes = iter.as_slice();
+        } else {
+            f(char::REPLACEMENT_CHARACTER);
+            bytes = &bytes[1..];
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod fast;
+pub(crate) mod line;
 pub(crate) mod slice;
 pub(crate) mod utf8;
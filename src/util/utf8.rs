@@ -176,7 +176,13 @@ impl<'i> Iterator for CharIter<'i> {
         if self.is_done() {
             None
         } else {
-            let result = match first_codepoint(self.head()) {
+            let head = self.head();
+            // ASCII chars don't require decoding.
+            if let Some(&b) = head.first().filter(|b| b.is_ascii()) {
+                self.forward += 1;
+                return Some(Ok(b as char));
+            }
+            let result = match first_codepoint(head) {
                 Ok(c) => {
                     let forward = self.forward.saturating_add(c.len_utf8());
                     // If the parsing of the character goes over the reader's
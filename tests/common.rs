@@ -34,6 +34,12 @@ macro_rules! input {
     };
 }
 
+macro_rules! input_bound {
+    ($input:expr) => {
+        dangerous::input(&$input).into_bound()
+    };
+}
+
 macro_rules! read_all {
     ($input:expr, $read_fn:expr) => {
         input!($input).read_all::<_, _, dangerous::Expected>($read_fn)
@@ -75,3 +81,33 @@ macro_rules! read_partial_err {
         read_partial!($input, $read_fn).unwrap_err()
     };
 }
+
+macro_rules! read_all_bound {
+    ($input:expr, $read_fn:expr) => {
+        input_bound!($input).read_all::<_, _, dangerous::Expected>($read_fn)
+    };
+}
+
+macro_rules! read_partial_bound {
+    ($input:expr, $read_fn:expr) => {
+        input_bound!($input).read_partial::<_, _, dangerous::Expected>($read_fn)
+    };
+}
+
+macro_rules! read_all_bound_ok {
+    ($input:expr, $read_fn:expr) => {
+        read_all_bound!($input, $read_fn).unwrap()
+    };
+}
+
+macro_rules! read_all_bound_err {
+    ($input:expr, $read_fn:expr) => {
+        read_all_bound!($input, $read_fn).unwrap_err()
+    };
+}
+
+macro_rules! read_partial_bound_ok {
+    ($input:expr, $read_fn:expr) => {
+        read_partial_bound!($input, $read_fn).unwrap()
+    };
+}
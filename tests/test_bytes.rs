@@ -0,0 +1,43 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::input::LengthPrefix;
+
+#[test]
+fn test_input_from_bytes() {
+    let shared = bytes::Bytes::from_static(b"\x02hi");
+    let body = dangerous::input(&shared)
+        .read_all::<_, _, Expected<'_>>(|r| r.take_length_prefixed(LengthPrefix::U8))
+        .unwrap();
+
+    assert_eq!(body, b"hi"[..]);
+}
+
+#[test]
+fn test_input_from_bytes_mut() {
+    let buf = bytes::BytesMut::from(&b"hi"[..]);
+
+    assert_eq!(dangerous::input(&buf), b"hi"[..]);
+}
+
+#[test]
+fn test_span_of_bytes() {
+    let shared = bytes::Bytes::from_static(b"\x05hello world");
+    let body = dangerous::input(&shared)
+        .read_partial::<_, _, Expected<'_>>(|r| r.take_length_prefixed(LengthPrefix::U8))
+        .unwrap()
+        .0;
+    let slice = body.span().of(shared.clone()).unwrap();
+
+    assert_eq!(slice, "hello");
+    assert_eq!(slice.as_ptr(), shared[1..].as_ptr());
+}
+
+#[test]
+fn test_span_of_bytes_outside() {
+    let shared = bytes::Bytes::from_static(b"hello");
+    let other = bytes::Bytes::from_static(b"world");
+
+    assert_eq!(Span::from(&other[..]).of(shared), None);
+}
@@ -0,0 +1,202 @@
+#[macro_use]
+mod common;
+
+use common::*;
+
+use dangerous::input::{FloatPolicy, LengthPrefix};
+
+#[derive(Debug, PartialEq, Read)]
+#[dangerous(be)]
+struct Packet<'a> {
+    #[dangerous(consume = b"PK", verify = is_supported, expected = "supported version")]
+    version: u8,
+    len: u16,
+    #[dangerous(len = len)]
+    body: &'a [u8],
+}
+
+fn is_supported(version: &u8) -> bool {
+    *version == 1
+}
+
+#[derive(Debug, PartialEq, Read)]
+struct Record<'a> {
+    header: Header,
+    #[dangerous(prefix = LengthPrefix::U8)]
+    name: &'a str,
+    #[dangerous(prefix = LengthPrefix::U8)]
+    raw: Bytes<'a>,
+    #[dangerous(len = 2)]
+    code: dangerous::String<'a>,
+    #[dangerous(le, policy = FloatPolicy::finite())]
+    ratio: f32,
+    trailer: [u8; 2],
+}
+
+#[derive(Debug, PartialEq, Read)]
+#[dangerous(le)]
+struct Header {
+    kind: i8,
+    #[dangerous(be)]
+    id: u32,
+    offset: i16,
+}
+
+#[derive(Debug, PartialEq, Read)]
+struct Signed<'a> {
+    len: i8,
+    #[dangerous(len = len)]
+    body: &'a [u8],
+}
+
+mod other {
+    use dangerous::{BytesReader, Error};
+
+    #[derive(Debug, PartialEq)]
+    pub struct Bytes(pub u8);
+
+    impl Bytes {
+        pub fn read<'i, E>(r: &mut BytesReader<'i, E>) -> Result<Self, E>
+        where
+            E: Error<'i>,
+        {
+            r.read_u8().map(Self)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Read)]
+struct Foreign {
+    bytes: other::Bytes,
+}
+
+#[test]
+fn test_derive_read() {
+    let packet = input!(b"PK\x01\x00\x02hi")
+        .read_all::<_, _, Expected<'_>>(Packet::read)
+        .unwrap();
+
+    assert_eq!(
+        packet,
+        Packet {
+            version: 1,
+            len: 2,
+            body: b"hi",
+        }
+    );
+}
+
+#[test]
+fn test_derive_read_nested() {
+    let input = input!(b"\xff\x00\x00\x00\x2a\x02\x00\x03bob\x01\xffab\x00\x00\x80\x3f\x0d\x0a");
+    let record = input.read_all::<_, _, Expected<'_>>(Record::read).unwrap();
+
+    assert_eq!(
+        record.header,
+        Header {
+            kind: -1,
+            id: 42,
+            offset: 2,
+        }
+    );
+    assert_eq!(record.name, "bob");
+    assert_eq!(record.raw, b"\xff"[..]);
+    assert_eq!(record.code.as_dangerous(), "ab");
+    assert_eq!(record.ratio, 1.0);
+    assert_eq!(record.trailer, *b"\r\n");
+}
+
+#[test]
+fn test_derive_read_retry() {
+    let error = input!(b"PK\x01\x00\x04hi")
+        .read_all::<_, _, Expected<'_>>(Packet::read)
+        .unwrap_err();
+
+    assert_eq!(error.to_retry_requirement(), RetryRequirement::new(2));
+}
+
+#[test]
+fn test_derive_read_invalid_len() {
+    let error = input!(b"\xffhi")
+        .read_all::<_, _, Expected<'_>>(Signed::read)
+        .unwrap_err();
+
+    assert_eq!(error.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{:#}", error),
+        indoc! {r#"
+            failed to read and expect a value: expected valid length
+            > [ff 'h' 'i']
+                  ^^^     
+            additional:
+              error line: 1, error offset: 1, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected body)
+              3. `read and expect a value` (expected valid length)"#
+        }
+    );
+}
+
+#[test]
+fn test_derive_read_foreign_bytes() {
+    let foreign = input!(b"\x01")
+        .read_all::<_, _, Expected<'_>>(Foreign::read)
+        .unwrap();
+
+    assert_eq!(foreign.bytes, other::Bytes(1));
+}
+
+#[test]
+fn test_derive_read_float_policy() {
+    let input = input!(b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x80\x7f\x00\x00");
+
+    assert!(input.read_all::<_, _, Expected<'_>>(Record::read).is_err());
+}
+
+#[test]
+fn test_derive_read_consume_display() {
+    let error = input!(b"ZK\x01\x00\x00")
+        .read_all::<_, _, Expected<'_>>(Packet::read)
+        .unwrap_err();
+
+    assert_str_eq!(
+        format!("{:#}", error),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "PK"
+            in:
+            > "ZK\u{1}\0\0"
+               ^^          
+            additional:
+              error line: 1, error offset: 0, input length: 5
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected version)
+              3. `consume input` (expected exact value)"#
+        }
+    );
+}
+
+#[test]
+fn test_derive_read_verify_display() {
+    let error = input!(b"PK\x02\x00\x00")
+        .read_all::<_, _, Expected<'_>>(Packet::read)
+        .unwrap_err();
+
+    assert_str_eq!(
+        format!("{:#}", error),
+        indoc! {r#"
+            failed to read and expect a value: expected supported version
+            > "PK\u{2}\0\0"
+                 ^^^^^     
+            additional:
+              error line: 1, error offset: 2, input length: 5
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected version)
+              3. `read and expect a value` (expected supported version)"#
+        }
+    );
+}
@@ -95,3 +95,83 @@ fn test_preferred_format_debug() {
     assert_eq!(format!("{:?}", PreferredFormat::Bytes), "Bytes");
     assert_eq!(format!("{:?}", PreferredFormat::BytesAscii), "BytesAscii");
 }
+
+#[test]
+#[cfg(feature = "color")]
+fn test_input_display_color() {
+    let input = input!(b"abc");
+    let span = input.clone().split_at_opt(1).unwrap().0.span();
+    let display = input.display().span(span, 16).underline();
+    assert_eq!(display.clone().to_string(), " ^^       ");
+    assert_eq!(
+        display.color(true).to_string(),
+        "\x1b[1;31m ^^       \x1b[0m"
+    );
+}
+
+#[test]
+fn test_hex_dump_full() {
+    use dangerous::display::PreferredFormat;
+    let input = input!(b"hello world\x00\xff0123456789");
+    assert_str_eq!(
+        input.display().format(PreferredFormat::HexDump).to_string(),
+        indoc! {"
+            00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 00 ff 30 31 32  |hello world..012|
+            00000010  33 34 35 36 37 38 39                              |3456789|"
+        }
+    );
+}
+
+#[test]
+fn test_hex_dump_head_tail() {
+    use dangerous::display::PreferredFormat;
+    let bytes: Vec<u8> = (0..64).collect();
+    let input = input!(bytes[..]);
+    assert_str_eq!(
+        input
+            .display()
+            .format(PreferredFormat::HexDump)
+            .head_tail(32)
+            .to_string(),
+        indoc! {"
+            00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|
+            ..
+            00000030  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|"
+        }
+    );
+    assert_str_eq!(
+        input
+            .display()
+            .format(PreferredFormat::HexDump)
+            .tail(16)
+            .to_string(),
+        indoc! {"
+            ..
+            00000030  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|"
+        }
+    );
+}
+
+#[test]
+fn test_hex_dump_span_underline() {
+    use dangerous::display::PreferredFormat;
+    let bytes: Vec<u8> = (0x20..0x80).collect();
+    let input = input!(bytes[..]);
+    let span = Span::from(&bytes[30..34]);
+    assert_str_eq!(
+        input
+            .display()
+            .format(PreferredFormat::HexDump)
+            .span(span, 32)
+            .underline()
+            .to_string(),
+        indoc! {r#"
+            ..
+            00000010  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|
+                                                                 ^^ ^^                 ^^
+            00000020  40 41 42 43 44 45 46 47  48 49 4a 4b 4c 4d 4e 4f  |@ABCDEFGHIJKLMNO|
+                      ^^ ^^                                              ^^
+            .."#
+        }
+    );
+}
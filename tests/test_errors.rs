@@ -590,3 +590,193 @@ fn test_invalid_error_details_span() {
         "#}
     );
 }
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_error_display_snippet() {
+    let error = read_all_err!("a = 1\nb = x\n\nc = 1", |r| {
+        r.repeat(|r| {
+            r.skip_until_consume(" = ")?;
+            r.consume("1")?;
+            r.consume_opt('\n');
+            Ok(())
+        })
+        .collect::<Result<Vec<_>, _>>()
+    });
+
+    assert_str_eq!(
+        format!("{}\n", error.display().snippet(true)),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "1"
+            in:
+             --> 2:5
+              |
+            1 | a = 1
+            2 | b = x
+              |     ^ expected exact value
+              | ----- read item at index 1
+            3 |
+              |
+            backtrace:
+              1. `read all input`
+              2. `read item at index 1`
+              3. `consume input` (expected exact value)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_error_display_snippet_multi_line() {
+    let error = read_all_err!("a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk", |r| r.take(2));
+
+    assert_str_eq!(
+        format!(
+            "{}\n",
+            error.display().snippet(true).snippet_context_lines(2)
+        ),
+        indoc! {r#"
+            failed to read all input: found 19 bytes when exactly no bytes was expected
+              --> 2:1
+               |
+             1 | a
+             2 | b
+               | ^ expected no trailing input
+             3 | c
+               | ^
+             4 | d
+               | ^
+            ...
+             9 | i
+               | ^
+            10 | j
+               | ^
+            11 | k
+               | ^
+               |
+            backtrace:
+              1. `read all input` (expected no trailing input)
+        "#}
+    );
+}
+
+#[test]
+#[cfg(all(feature = "color", feature = "full-backtrace"))]
+fn test_error_display_color() {
+    let error: Expected = trigger_expected_value_str();
+
+    assert_str_eq!(
+        format!("{}", error.display().color(true).banner(true)),
+        indoc! {"
+
+            \x1b[1;31m-- INPUT ERROR ---------------------------------------------\x1b[0m
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > \x1b[32m\"123\"\x1b[0m
+            in:
+            > \"hello world\"
+              \x1b[1;31m ^^^         \x1b[0m
+            additional:
+              error line: 1, error offset: 0, input length: 11
+            backtrace:
+              1. `\x1b[36mread all input\x1b[0m`
+              2. `\x1b[36m<context>\x1b[0m` (expected \x1b[32mhi\x1b[0m)
+              3. `\x1b[36mconsume input\x1b[0m` (expected \x1b[32mexact value\x1b[0m)
+            \x1b[1;31m------------------------------------------------------------\x1b[0m
+        "}
+    );
+}
+
+#[test]
+#[cfg(all(feature = "json", feature = "full-backtrace"))]
+fn test_error_json() {
+    let error: Expected = trigger_expected_value_str();
+
+    assert_str_eq!(
+        error.json().to_string(),
+        concat!(
+            r#"{"operation":"consume input","#,
+            r#""description":"found a different value to the exact expected","#,
+            r#""expected":"\"123\"","#,
+            r#""input":{"length":11,"is_string":true},"#,
+            r#""span":{"start":0,"end":3},"#,
+            r#""start":{"line":1,"column":1},"#,
+            r#""end":{"line":1,"column":4},"#,
+            r#""backtrace":["#,
+            r#"{"depth":1,"operation":"read all input","expected":null,"span":{"start":0,"end":11}},"#,
+            r#"{"depth":2,"operation":"<context>","expected":"hi","span":null},"#,
+            r#"{"depth":3,"operation":"consume input","expected":"exact value","span":{"start":0,"end":3}}"#,
+            r#"]}"#,
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_error_json_multi_line() {
+    let error: Expected = input!("a\n\"é\tb")
+        .read_all(|r| {
+            r.skip_until_consume('\n')?;
+            r.skip(3)?;
+            r.consume('c')
+        })
+        .unwrap_err();
+    let json = error.json().to_string();
+
+    assert!(json.contains(r#""expected":"\"c\"""#));
+    assert!(json.contains(r#""span":{"start":6,"end":7}"#));
+    assert!(json.contains(r#""start":{"line":2,"column":4}"#));
+    assert!(json.contains(r#""end":{"line":2,"column":5}"#));
+}
+
+#[test]
+#[cfg(all(feature = "json", feature = "alloc"))]
+fn test_error_json_agrees_with_line_index() {
+    use dangerous::input::{ColumnUnit, LineIndex};
+
+    let input = input!(b"a\r\n\xff\xffbc");
+    let error: Expected = input
+        .clone()
+        .read_all(|r| {
+            r.skip_until_consume(b'\n')?;
+            r.skip(3)?;
+            r.consume(b'x')
+        })
+        .unwrap_err();
+    let json = error.json().to_string();
+    let index = LineIndex::new(&input).column_unit(ColumnUnit::Char);
+    let position = index.position(6).unwrap()..index.position(7).unwrap();
+
+    assert!(json.contains(r#""span":{"start":6,"end":7}"#));
+    assert!(json.contains(&format!(
+        r#""start":{{"line":{},"column":{}}}"#,
+        position.start.line, position.start.column
+    )));
+    assert!(json.contains(&format!(
+        r#""end":{{"line":{},"column":{}}}"#,
+        position.end.line, position.end.column
+    )));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_error_json_invalid_span() {
+    struct BadExternalError;
+
+    impl<'i> External<'i> for BadExternalError {
+        fn span(&self) -> Option<Span> {
+            Some("not-a-valid-span".into())
+        }
+    }
+
+    let error = read_all_err!("hello world", |r| {
+        r.try_external("value", |_| {
+            Result::<(usize, ()), BadExternalError>::Err(BadExternalError)
+        })
+    });
+    let json = error.json().to_string();
+
+    assert!(json.contains(r#""span":null,"start":null,"end":null"#));
+}
@@ -4,6 +4,7 @@
 mod common;
 
 use common::*;
+use dangerous::input::Bound;
 
 #[test]
 fn test_as_dangerous() {
@@ -57,6 +58,31 @@ fn test_to_dangerous_str_expected_length() {
     assert_eq!(err.to_retry_requirement(), None);
 }
 
+#[test]
+fn test_to_dangerous_str_after_ascii() {
+    let mut bytes = b"a".repeat(100);
+    bytes.extend_from_slice("é".as_bytes());
+    assert_eq!(
+        input!(bytes[..])
+            .to_dangerous_str::<Expected>()
+            .unwrap()
+            .len(),
+        102
+    );
+    // Cut short
+    bytes.push(0b1101_1111);
+    let err = input!(bytes[..])
+        .to_dangerous_str::<Expected>()
+        .unwrap_err();
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    // Invalid
+    bytes.push(0xff);
+    let err = input!(bytes[..])
+        .to_dangerous_str::<Expected>()
+        .unwrap_err();
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
 #[test]
 fn test_read_all() {
     // Valid
@@ -114,3 +140,91 @@ fn test_read_infallible() {
         (input!(b"hello"), input!(b"1"))
     );
 }
+
+#[test]
+fn test_split() {
+    let split: Vec<_> = input!(b"a,bc,,d").split(b',').collect();
+    assert_eq!(
+        split,
+        [input!(b"a"), input!(b"bc"), input!(b""), input!(b"d")]
+    );
+    let split: Vec<_> = input!(b"a,").split(b',').collect();
+    assert_eq!(split, [input!(b"a"), input!(b"")]);
+    let split: Vec<_> = input!(b"").split(b',').collect();
+    assert_eq!(split, [input!(b"")]);
+    let split: Vec<_> = input!("a::b").split("::").collect();
+    assert_eq!(split, [input!("a"), input!("b")]);
+}
+
+#[test]
+fn test_split_bound() {
+    let split: Vec<_> = input!(b"a,b").split(b',').collect();
+    assert_eq!(split[0].bound(), Bound::StartEnd);
+    assert_eq!(split[1].bound(), Bound::Start);
+    let split: Vec<_> = input!(b"a,b").into_bound().split(b',').collect();
+    assert_eq!(split[1].bound(), Bound::StartEnd);
+}
+
+#[test]
+fn test_split_terminator() {
+    let split: Vec<_> = input!(b"a,b,").split_terminator(b',').collect();
+    assert_eq!(split, [input!(b"a"), input!(b"b")]);
+    let split: Vec<_> = input!(b"a,,b").split_terminator(b',').collect();
+    assert_eq!(split, [input!(b"a"), input!(b""), input!(b"b")]);
+    let split: Vec<_> = input!(b",").split_terminator(b',').collect();
+    assert_eq!(split, [input!(b"")]);
+    assert_eq!(input!(b"").split_terminator(b',').count(), 0);
+}
+
+#[test]
+fn test_splitn() {
+    let split: Vec<_> = input!(b"a,b,c").splitn(2, b',').collect();
+    assert_eq!(split, [input!(b"a"), input!(b"b,c")]);
+    let split: Vec<_> = input!(b"a,b,c").splitn(5, b',').collect();
+    assert_eq!(split, [input!(b"a"), input!(b"b"), input!(b"c")]);
+    let split: Vec<_> = input!(b"a,b,c").splitn(1, b',').collect();
+    assert_eq!(split, [input!(b"a,b,c")]);
+    assert_eq!(input!(b"a,b,c").splitn(0, b',').count(), 0);
+}
+
+#[test]
+fn test_split_at_last() {
+    assert_eq!(
+        input_bound!("archive.tar.gz").split_at_last('.'),
+        Some((input!("archive.tar"), input!(".gz")))
+    );
+    assert_eq!(input_bound!(b"archive").split_at_last(b'.'), None);
+
+    let (head, tail) = input_bound!(b"a,b").split_at_last(b',').unwrap();
+    assert_eq!(head.bound(), Bound::StartEnd);
+    assert_eq!(tail.bound(), Bound::StartEnd);
+    // Unbound
+    assert_eq!(input!(b"a,b").split_at_last(b','), None);
+}
+
+#[test]
+fn test_strip_suffix() {
+    assert_eq!(
+        input_bound!(b"hello.txt").strip_suffix(&b".txt"[..]),
+        Some(input!(b"hello"))
+    );
+    assert_eq!(
+        input_bound!("caf\u{e9}").strip_suffix('\u{e9}'),
+        Some(input!("caf"))
+    );
+    assert_eq!(input_bound!(b"hello").strip_suffix(b'!'), None);
+    assert_eq!(input_bound!(b"").strip_suffix(b'!'), None);
+    // Unbound
+    assert_eq!(input!(b"hello!").strip_suffix(b'!'), None);
+}
+
+#[test]
+fn test_lines() {
+    let lines: Vec<_> = input!("a\nb\r\n\nc").lines().collect();
+    assert_eq!(lines, [input!("a"), input!("b"), input!(""), input!("c")]);
+    let lines: Vec<_> = input!(b"a\r\n").lines().collect();
+    assert_eq!(lines, [input!(b"a")]);
+    let lines: Vec<_> = input!(b"a\r").lines().collect();
+    assert_eq!(lines, [input!(b"a\r")]);
+    assert_eq!(input!(b"").lines().count(), 0);
+}
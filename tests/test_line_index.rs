@@ -0,0 +1,83 @@
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::input::{ColumnUnit, LineColumn, LineIndex};
+
+fn pos(line: usize, column: usize) -> LineColumn {
+    LineColumn { line, column }
+}
+
+#[test]
+fn test_line_count() {
+    assert_eq!(LineIndex::new(&input!(b"")).line_count(), 1);
+    assert_eq!(LineIndex::new(&input!(b"a")).line_count(), 1);
+    assert_eq!(LineIndex::new(&input!(b"a\n")).line_count(), 2);
+    assert_eq!(LineIndex::new(&input!(b"a\r\nb\nc")).line_count(), 3);
+}
+
+#[test]
+fn test_line_span() {
+    let input = "ab\r\ncd\n\nef";
+    let index = LineIndex::new(&input!(input));
+    assert_eq!(index.line_span(0), None);
+    assert_eq!(index.line_span(1), Some(Span::from(&input[0..2])));
+    assert_eq!(index.line_span(2), Some(Span::from(&input[4..6])));
+    assert_eq!(index.line_span(3), Some(Span::from(&input[7..7])));
+    assert_eq!(index.line_span(4), Some(Span::from(&input[8..10])));
+    assert_eq!(index.line_span(5), None);
+}
+
+#[test]
+fn test_position_bytes() {
+    let index = LineIndex::new(&input!(b"ab\ncd\n"));
+    assert_eq!(index.position(0), Some(pos(1, 1)));
+    assert_eq!(index.position(2), Some(pos(1, 3)));
+    assert_eq!(index.position(3), Some(pos(2, 1)));
+    assert_eq!(index.position(4), Some(pos(2, 2)));
+    assert_eq!(index.position(6), Some(pos(3, 1)));
+    assert_eq!(index.position(7), None);
+}
+
+#[test]
+fn test_position_column_units() {
+    // `é` is 2 bytes, `😀` is 4 bytes and 2 UTF-16 code units, `日` is
+    // 3 bytes with a display width of 2.
+    let input = input!("x\né😀日!");
+    let end = input.byte_len() - 1;
+    let index = LineIndex::new(&input);
+    assert_eq!(index.position(end), Some(pos(2, 10)));
+    let index = index.column_unit(ColumnUnit::Char);
+    assert_eq!(index.position(end), Some(pos(2, 4)));
+    let index = index.column_unit(ColumnUnit::Utf16);
+    assert_eq!(index.position(end), Some(pos(2, 5)));
+    #[cfg(feature = "unicode")]
+    {
+        let index = index.column_unit(ColumnUnit::Width);
+        assert_eq!(index.position(end), Some(pos(2, 6)));
+    }
+}
+
+#[test]
+fn test_position_invalid_utf8() {
+    let index = LineIndex::new(&input!(b"\xff\xffa")).column_unit(ColumnUnit::Char);
+    assert_eq!(index.position(3), Some(pos(1, 4)));
+}
+
+#[test]
+fn test_resolve() {
+    let input = "one\ntwo\nthree";
+    let index = LineIndex::new(&input!(input));
+    assert_eq!(
+        index.resolve(Span::from(&input[5..10])),
+        Some(pos(2, 2)..pos(3, 3))
+    );
+    assert_eq!(index.resolve(Span::from("other")), None);
+}
+
+#[test]
+fn test_line_column_display() {
+    assert_eq!(pos(2, 7).to_string(), "2:7");
+}
@@ -2,6 +2,9 @@
 mod common;
 
 use common::*;
+#[cfg(feature = "alloc")]
+use dangerous::input::Needles;
+use dangerous::input::{ByteSet, Bytes, IgnoreAsciiCase, Pattern};
 
 ///////////////////////////////////////////////////////////////////////////////
 // reject: bytes function
@@ -57,6 +60,18 @@ fn test_reject_string_fn_none() {
     );
 }
 
+#[test]
+fn test_reject_string_fn_non_ascii() {
+    assert_eq!(
+        read_all_ok!("héllo wörld", |r| {
+            let v = r.take_while(|c: char| c.is_alphabetic());
+            r.consume(" wörld")?;
+            Ok(v)
+        }),
+        "héllo"[..]
+    );
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // reject: u8
 
@@ -72,6 +87,20 @@ fn test_reject_u8() {
     );
 }
 
+#[test]
+fn test_reject_u8_across_blocks() {
+    for len in 0..200 {
+        let mut bytes = vec![b'1'; len];
+        bytes.extend_from_slice(b"!1");
+        let v = read_all_ok!(bytes[..], |r| {
+            let v = r.take_while(b'1');
+            r.consume(b"!1")?;
+            Ok(v)
+        });
+        assert_eq!(v.len(), len);
+    }
+}
+
 #[test]
 fn test_reject_u8_none() {
     assert_eq!(
@@ -198,6 +227,20 @@ fn test_reject_bytes() {
     );
 }
 
+#[test]
+fn test_reject_bytes_across_blocks() {
+    for len in 0..100 {
+        let mut bytes = b"123".repeat(len);
+        bytes.extend_from_slice(b"12!");
+        let v = read_all_ok!(bytes[..], |r| {
+            let v = r.take_while(b"123");
+            r.consume(b"12!")?;
+            Ok(v)
+        });
+        assert_eq!(v.len(), len * 3);
+    }
+}
+
 #[test]
 fn test_reject_bytes_none() {
     assert_eq!(
@@ -369,6 +412,46 @@ fn test_match_string_empty() {
     });
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// match: string function
+
+#[test]
+fn test_match_string_fn() {
+    assert_eq!(
+        read_partial_ok!("ab;c", |r| Ok(r.take_until_opt(|c: char| c == ';'))),
+        (input!("ab"), input!(";c"))
+    );
+}
+
+#[test]
+fn test_match_string_fn_non_ascii() {
+    assert_eq!(
+        read_partial_ok!("h\u{e9}llo w\u{f6}rld", |r| Ok(
+            r.take_until_opt(|c: char| !c.is_ascii())
+        )),
+        (input!("h"), input!("\u{e9}llo w\u{f6}rld"))
+    );
+}
+
+#[test]
+fn test_match_string_fn_none() {
+    assert_eq!(
+        read_partial_ok!("abc", |r| Ok(r.take_until_opt(|c: char| c == ';'))),
+        (input!("abc"), input!(""))
+    );
+}
+
+#[test]
+fn test_match_string_fn_skip() {
+    assert_eq!(
+        read_all_ok!("ab;c", |r| {
+            r.skip_until_opt(|c: char| c == ';');
+            r.consume(";c")
+        }),
+        ()
+    );
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // reject: bytes regex
 
@@ -546,3 +629,389 @@ fn test_match_string_regex_none() {
         "!!!!"[..]
     )
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// byte set
+
+const STOP: ByteSet<'static> = ByteSet::new(b"\r\n;#");
+
+#[test]
+fn test_byte_set_contains() {
+    assert!(STOP.contains(b';'));
+    assert!(!STOP.contains(b'a'));
+    assert!(ByteSet::new(&[0xff]).contains(0xff));
+    assert!(!ByteSet::new(b"").contains(0));
+}
+
+#[test]
+fn test_match_bytes_set() {
+    for len in 1..=4 {
+        let set = ByteSet::new(&b"#;\r\n"[..len]);
+        assert_eq!(
+            read_partial_ok!(b"key=value#", |r| r.take_until(set)).0,
+            b"key=value"[..]
+        );
+    }
+}
+
+#[test]
+fn test_match_bytes_set_none() {
+    let err = read_all_err!(b"key", |r| r.take_until(STOP));
+
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_eq!(
+        read_all_err!(b"", |r| r.take_until(STOP)).to_retry_requirement(),
+        RetryRequirement::new(1)
+    );
+}
+
+#[test]
+fn test_reject_bytes_set() {
+    assert_eq!(
+        read_partial_ok!(b"\r\n\r\nbody", |r| Ok(r.take_while(STOP))).0,
+        b"\r\n\r\n"[..]
+    );
+}
+
+#[test]
+fn test_match_string_set_ascii_only() {
+    let set = ByteSet::new(&[b';', 0xc3, 0xa9]);
+
+    assert_eq!(
+        read_partial_ok!("caf\u{e9};", |r| r.take_until(set)).0,
+        "caf\u{e9}"
+    );
+    assert_eq!(
+        read_partial_ok!(";;\u{e9}", |r| Ok(r.take_while(set))).0,
+        ";;"
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_match_bytes_set_err_display() {
+    let err = read_all_err!(b"key", |r| r.take_until(ByteSet::new(b";#")));
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to take input until a pattern matches: found none of the expected values
+            expected any of:
+            > ";#"
+            in:
+            > "key"
+               ^^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `take input until a pattern matches` (expected pattern match)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// needles
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_bytes_needles() {
+    let needles = Needles::new(&["cd", "bcd", "b"]);
+
+    assert_eq!(needles.len(), 3);
+    assert_eq!(
+        read_partial_ok!(b"abcde", |r| Ok(r.take_until_opt(&needles))).0,
+        b"a"[..]
+    );
+    assert_eq!(
+        read_partial_ok!(b"abcde", |r| Ok(r.take_until_consume_opt(&needles))).1,
+        b"e"[..]
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_bytes_needles_none() {
+    let needles = Needles::new(&["x", "yz", ""]);
+
+    assert_eq!(needles.len(), 2);
+    assert_eq!(
+        read_partial_ok!(b"abcy", |r| Ok(r.take_until_opt(&needles))).0,
+        b"abcy"[..]
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_reject_bytes_needles() {
+    let needles = Needles::new(&["ab", "c"]);
+
+    assert_eq!(
+        read_partial_ok!(b"abcabab!", |r| Ok(r.take_while(&needles))).0,
+        b"abcabab"[..]
+    );
+    assert_eq!(
+        read_partial_ok!(b"!", |r| Ok(r.take_while(&Needles::new(&[""])))).0,
+        b""[..]
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_string_needles_skips_invalid() {
+    let needles = Needles::new(&[&[0xa9][..], b"!"]);
+
+    assert_eq!(
+        read_partial_ok!("caf\u{e9}!", |r| Ok(r.take_until_opt(&needles))).0,
+        "caf\u{e9}"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_needles_erroring() {
+    let needles = Needles::new(&["=", ": "]);
+
+    assert_eq!(
+        read_partial_ok!(b"key: value", |r| r.take_until(&needles)),
+        (input!(b"key"), input!(b": value"))
+    );
+    assert_eq!(
+        read_partial_ok!(b"key=value", |r| r.skip_until(&needles)).1,
+        input!(b"=value")
+    );
+    assert_eq!(
+        read_partial_ok!(b"key: value", |r| r.take_until_consume(&needles)),
+        (input!(b"key"), input!(b"value"))
+    );
+    assert_eq!(
+        read_partial_bound_ok!(b"a=b: c", |r| r.take_until_last(&needles)),
+        (input!(b"a=b"), input!(b": c"))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_needles_err() {
+    let needles = Needles::new(&["ab", "cde"]);
+
+    let err = read_all_err!(b"xyz", |r| r.take_until(&needles));
+    assert_eq!(err.to_retry_requirement(), None);
+    let err = read_all_err!(b"", |r| r.skip_until(&needles));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "full-backtrace"))]
+fn test_match_needles_err_display() {
+    let needles = Needles::new(&["=", ": "]);
+    let err = read_all_err!("key", |r| r.take_until(&needles));
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to take input until a pattern matches: found none of the expected values
+            expected any of:
+            > "=", ": "
+            in:
+            > "key"
+               ^^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `take input until a pattern matches` (expected pattern match)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// IgnoreAsciiCase
+
+#[test]
+fn test_match_bytes_ignore_ascii_case() {
+    assert_eq!(
+        read_partial_ok!(b"Host: a\r\nHOST: b", |r| {
+            r.skip_until_consume(IgnoreAsciiCase::new(b"host: "))?;
+            r.take_until(IgnoreAsciiCase::new(b"\r\n"))
+        }),
+        (input!(b"a"), input!(b"\r\nHOST: b"))
+    );
+}
+
+#[test]
+fn test_match_string_ignore_ascii_case() {
+    assert_eq!(
+        read_partial_ok!("a CRLF b", |r| r.take_until(IgnoreAsciiCase::new("crlf"))).0,
+        "a "
+    );
+}
+
+#[test]
+fn test_reject_bytes_ignore_ascii_case() {
+    assert_eq!(
+        read_partial_ok!(
+            b"abABaBx",
+            |r| Ok(r.take_while(IgnoreAsciiCase::new(b"ab")))
+        )
+        .0,
+        b"abABaB"[..]
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// match rev
+
+#[test]
+fn test_match_rev_bytes_fn() {
+    assert_eq!(
+        read_partial_bound_ok!(b"a1b2c", |r| Ok(
+            r.take_until_last_opt(|b: u8| b.is_ascii_digit())
+        )),
+        (input!(b"a1b"), input!(b"2c"))
+    );
+}
+
+#[test]
+fn test_match_rev_string_fn() {
+    assert_eq!(
+        read_partial_bound_ok!("a\u{e9}b\u{e9}c", |r| Ok(
+            r.take_until_last_opt(|c: char| c == '\u{e9}')
+        )),
+        (input!("a\u{e9}b"), input!("\u{e9}c"))
+    );
+}
+
+#[test]
+fn test_match_string_fn_agrees_with_rev() {
+    assert_eq!(
+        read_partial_bound_ok!("ab,cd,ef", |r| Ok(r.take_until_opt(|c: char| c == ','))),
+        (input!("ab"), input!(",cd,ef"))
+    );
+    assert_eq!(
+        read_partial_bound_ok!(
+            "ab,cd,ef",
+            |r| Ok(r.take_until_last_opt(|c: char| c == ','))
+        ),
+        (input!("ab,cd"), input!(",ef"))
+    );
+}
+
+#[test]
+fn test_match_rev_string_char() {
+    assert_eq!(
+        read_partial_bound_ok!("a/b\u{e9}/c", |r| r.take_until_last('/')),
+        (input!("a/b\u{e9}"), input!("/c"))
+    );
+}
+
+#[test]
+fn test_match_rev_string_str() {
+    assert_eq!(
+        read_partial_bound_ok!("a--b--c", |r| r.take_until_last("--")),
+        (input!("a--b"), input!("--c"))
+    );
+}
+
+#[test]
+fn test_match_rev_bytes_set() {
+    const SET: ByteSet<'static> = ByteSet::new(b"/\\");
+
+    assert_eq!(
+        read_partial_bound_ok!(b"a\\b/c", |r| r.take_until_last(SET)),
+        (input!(b"a\\b"), input!(b"/c"))
+    );
+    assert_eq!(
+        read_partial_bound_ok!(b"a\\b/c", |r| r.take_until_last(ByteSet::new(b"\\bx"))),
+        (input!(b"a\\"), input!(b"b/c"))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_match_rev_bytes_needles() {
+    let needles = Needles::new(&["b", "bc", "x"]);
+
+    assert_eq!(
+        read_partial_bound_ok!(b"abcabc", |r| Ok(r.take_until_last_opt(&needles))),
+        (input!(b"abca"), input!(b"bc"))
+    );
+}
+
+#[test]
+fn test_match_rev_ignore_ascii_case() {
+    assert_eq!(
+        read_partial_bound_ok!(b"a.ZIP.zip", |r| r
+            .take_until_last(IgnoreAsciiCase::new(b".ZIP"))),
+        (input!(b"a.ZIP"), input!(b".zip"))
+    );
+}
+
+#[test]
+fn test_match_rev_custom() {
+    struct Comma;
+
+    unsafe impl<'i> Pattern<Bytes<'i>> for Comma {
+        fn find_match(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+            b','.find_match(input)
+        }
+
+        fn find_match_rev(self, input: &Bytes<'i>) -> Option<(usize, usize)> {
+            b','.find_match_rev(input)
+        }
+
+        fn find_reject(self, input: &Bytes<'i>) -> Option<usize> {
+            b','.find_reject(input)
+        }
+    }
+
+    assert_eq!(
+        read_partial_bound_ok!(b"a,b", |r| Ok(r.take_until_opt(Comma))),
+        (input!(b"a"), input!(b",b"))
+    );
+    assert_eq!(
+        read_partial_bound_ok!(b"a,b,c", |r| Ok(r.take_until_last_opt(Comma))),
+        (input!(b"a,b"), input!(b",c"))
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn test_match_rev_regex() {
+    let regex = regex::Regex::new("[0-9]+").unwrap();
+
+    assert_eq!(
+        read_partial_bound_ok!("a12b345c", |r| Ok(r.take_until_last_opt(&regex))),
+        (input!("a12b34"), input!("5c"))
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn test_match_rev_regex_overlapping() {
+    let regex = regex::Regex::new("aa").unwrap();
+    assert_eq!(
+        input!("aaa").into_bound().split_at_last(&regex),
+        input!("aaa").into_bound().split_at_last("aa"),
+    );
+    assert_eq!(
+        input!("aaa").into_bound().split_at_last(&regex),
+        Some((input!("a"), input!("aa")))
+    );
+
+    let regex = regex::bytes::Regex::new("aa").unwrap();
+    assert_eq!(
+        input!(b"aaa").into_bound().split_at_last(&regex),
+        Some((input!(b"a"), input!(b"aa")))
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn test_match_rev_regex_multi_byte() {
+    let regex = regex::Regex::new("\\u{e9}|.").unwrap();
+    assert_eq!(
+        input!("a\u{e9}").into_bound().split_at_last(&regex),
+        Some((input!("a"), input!("\u{e9}")))
+    );
+}
@@ -394,6 +394,139 @@ fn test_consume_opt_false() {
     }));
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::consume_suffix
+
+#[test]
+fn test_consume_suffix() {
+    assert_eq!(
+        read_all_bound_ok!(b"hello\r\n", |r| {
+            r.consume_suffix(b"\r\n")?;
+            Ok(r.take_remaining())
+        }),
+        input!(b"hello")
+    );
+}
+
+#[test]
+fn test_consume_suffix_bound() {
+    assert_eq!(
+        read_all_bound_ok!(b"hello!", |r| {
+            r.consume_suffix(b'!')?;
+            Ok(r.take_remaining().bound())
+        }),
+        Bound::StartEnd
+    );
+}
+
+#[test]
+fn test_consume_suffix_unbound() {
+    assert_eq!(
+        read_all_err!(b"data\xab\xcd", |r| { r.consume_suffix(b"\xab\xcd") })
+            .to_retry_requirement(),
+        RetryRequirement::new(1)
+    );
+}
+
+#[test]
+fn test_consume_suffix_different_value() {
+    assert_eq!(
+        read_all_bound_err!(b"hello\n", |r| { r.consume_suffix(b"\r\n") }).to_retry_requirement(),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_consume_suffix_err_display() {
+    let err = read_all_bound_err!(b"hello\n", |r| { r.consume_suffix(b"\r\n") });
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to consume input from the end: found a different value to the exact expected
+            expected:
+            > "\r\n"
+            in:
+            > "hello\n"
+                   ^^^ 
+            additional:
+              error line: 1, error offset: 4, input length: 6
+            backtrace:
+              1. `read all input`
+              2. `consume input from the end` (expected exact value)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::consume_suffix_opt
+
+#[test]
+fn test_consume_suffix_opt() {
+    assert_eq!(
+        read_all_bound_ok!(b"hello", |r| {
+            assert!(!r.consume_suffix_opt(b"hell"));
+            assert!(r.consume_suffix_opt(b"llo"));
+            Ok(r.take_remaining())
+        }),
+        input!(b"he")
+    );
+    assert_eq!(
+        read_all_ok!(b"hello", |r| {
+            assert!(!r.consume_suffix_opt(b"llo"));
+            Ok(r.take_remaining())
+        }),
+        input!(b"hello")
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::take_until_last
+
+#[test]
+fn test_take_until_last() {
+    assert_eq!(
+        read_partial_bound_ok!(b"a,b,c", |r| r.take_until_last(b',')),
+        (input!(b"a,b"), input!(b",c"))
+    );
+}
+
+#[test]
+fn test_take_until_last_unbound() {
+    assert_eq!(
+        read_all_err!(b"a,b,c", |r| { r.take_until_last(b',') }).to_retry_requirement(),
+        RetryRequirement::new(1)
+    );
+}
+
+#[test]
+fn test_take_until_last_none() {
+    assert_eq!(
+        read_all_bound_err!(b"abc", |r| { r.take_until_last(b',') }).to_retry_requirement(),
+        None
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::take_until_last_opt
+
+#[test]
+fn test_take_until_last_opt() {
+    assert_eq!(
+        read_partial_bound_ok!(b"a::b::c", |r| Ok(r.take_until_last_opt(b"::"))),
+        (input!(b"a::b"), input!(b"::c"))
+    );
+    assert_eq!(
+        read_partial_bound_ok!(b"abc", |r| Ok(r.take_until_last_opt(b"::"))),
+        (input!(b"abc"), input!(b""))
+    );
+    assert_eq!(
+        read_partial_ok!(b"a::b", |r| Ok(r.take_until_last_opt(b"::"))),
+        (input!(b"a::b"), input!(b""))
+    );
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::verify
 
@@ -571,3 +704,138 @@ fn test_error() {
         Ok(())
     });
 }
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::repeat
+
+#[test]
+fn test_repeat() {
+    assert_eq!(
+        read_all_ok!(b"abc", |r| r
+            .repeat(|r| r.read())
+            .collect::<Result<Vec<_>, _>>()),
+        b"abc"
+    );
+}
+
+#[test]
+fn test_repeat_no_progress() {
+    assert_eq!(
+        read_partial_ok!(b"abc", |r| {
+            r.repeat(|r| Ok(r.take_while(|c: u8| c == b'z')))
+                .collect::<Result<Vec<_>, _>>()
+        }),
+        (vec![input!(b"")], input!(b"abc"))
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::repeat_n
+
+#[test]
+fn test_repeat_n() {
+    assert_eq!(
+        read_partial_ok!(b"abc", |r| r
+            .repeat_n(2, |r| r.read())
+            .collect::<Result<Vec<_>, _>>()),
+        (b"ab".to_vec(), input!(b"c"))
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_repeat_n_item_context() {
+    let err = read_all_err!(b"aab", |r| {
+        r.repeat_n(3, |r| r.consume(b'a'))
+            .collect::<Result<Vec<_>, _>>()
+    });
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > [61]
+            in:
+            > [61 61 62]
+                     ^^ 
+            additional:
+              error offset: 2, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `read item at index 2`
+              3. `consume input` (expected exact value)
+        "#}
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::separated
+
+#[test]
+fn test_separated() {
+    assert_eq!(
+        read_partial_ok!(b"a,b,c;", |r| {
+            r.separated(b',', |r| r.read())
+                .collect::<Result<Vec<_>, _>>()
+        }),
+        (b"abc".to_vec(), input!(b";"))
+    );
+}
+
+#[test]
+fn test_separated_trailing_separator() {
+    let _ = read_all_err!(b"a,", |r| {
+        r.separated(b',', |r| r.read())
+            .collect::<Result<Vec<_>, _>>()
+    });
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::many_until
+
+#[test]
+fn test_many_until() {
+    assert_eq!(
+        read_partial_ok!(b"ab;c", |r| {
+            r.many_until(b';', |r| r.read())
+                .collect::<Result<Vec<_>, _>>()
+        }),
+        (b"ab".to_vec(), input!(b"c"))
+    );
+}
+
+#[test]
+fn test_many_until_empty() {
+    assert_eq!(
+        read_all_ok!(b";", |r| {
+            r.many_until(b';', |r| r.read())
+                .collect::<Result<Vec<_>, _>>()
+        }),
+        vec![]
+    );
+}
+
+#[test]
+fn test_many_until_missing_terminator() {
+    let err = read_all_err!(b"ab", |r| {
+        r.many_until(b';', |r| r.read())
+            .collect::<Result<Vec<_>, _>>()
+    });
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+}
+
+#[test]
+fn test_many_until_no_progress() {
+    let err = read_all_err!(b"ab;", |r| {
+        r.many_until(b';', |r| Ok(r.take_while(b'x').len()))
+            .collect::<Result<Vec<_>, _>>()
+    });
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_eq!(
+        read_all_ok!(b";", |r| {
+            r.many_until(b';', |r| Ok(r.take_while(b'x').len()))
+                .collect::<Result<Vec<_>, _>>()
+        }),
+        vec![]
+    );
+}
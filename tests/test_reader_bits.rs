@@ -0,0 +1,199 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::input::BitOrder;
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::bits
+
+#[test]
+fn test_bits_realigns() {
+    assert_eq!(
+        read_all_ok!(&[0b1010_0000, 0x42], |r| {
+            let bits = r.bits(BitOrder::MsbFirst, |r| r.read_bits(3))?;
+            Ok((bits, r.read_u8()?))
+        }),
+        (0b101, 0x42)
+    );
+}
+
+#[test]
+fn test_bits_aligned_does_not_skip() {
+    assert_eq!(
+        read_all_ok!(&[0xff, 0x42], |r| {
+            let bits = r.bits(BitOrder::MsbFirst, |r| r.read_bits(8))?;
+            Ok((bits, r.read_u8()?))
+        }),
+        (0xff, 0x42)
+    );
+}
+
+#[test]
+fn test_bits_err_leaves_reader() {
+    assert_eq!(
+        read_all_ok!(&[0xff], |r| {
+            assert!(r
+                .recover(|r| r.bits(BitOrder::MsbFirst, |r| r.read_bits(9)))
+                .is_none());
+            r.read_u8()
+        }),
+        0xff
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_bits_err_context() {
+    let err = read_all_err!(&[0xff], |r| {
+        r.bits(BitOrder::MsbFirst, |r| {
+            r.read_bits(4)?;
+            r.context("field", |r| r.read_bits(8))
+        })
+    });
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to read a number of bits: found 1 byte when at least 2 bytes was expected
+            > [ff]
+               ^^ 
+            additional:
+              error offset: 0, input length: 1
+            backtrace:
+              1. `read all input`
+              2. `read input as bits`
+              3. `<context>` (expected field)
+              4. `read a number of bits` (expected enough input for bits)
+        "#}
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// BitReader::read_bits
+
+#[test]
+fn test_read_bits_msb_first() {
+    assert_eq!(
+        read_all_ok!(&[0b1100_1010, 0b0111_0001], |r| {
+            r.bits(BitOrder::MsbFirst, |r| {
+                Ok((r.read_bits(3)?, r.read_bits(10)?, r.read_bits(3)?))
+            })
+        }),
+        (0b110, 0b01_0100_1110, 0b001)
+    );
+}
+
+#[test]
+fn test_read_bits_lsb_first() {
+    assert_eq!(
+        read_all_ok!(&[0b1100_1010, 0b0111_0001], |r| {
+            r.bits(BitOrder::LsbFirst, |r| {
+                Ok((r.read_bits(3)?, r.read_bits(10)?, r.read_bits(3)?))
+            })
+        }),
+        (0b010, 0b10_0011_1001, 0b011)
+    );
+}
+
+#[test]
+fn test_read_bits_64() {
+    let bytes = [0x80, 0, 0, 0, 0, 0, 0, 0x01, 0xff];
+    assert_eq!(
+        read_all_ok!(&bytes, |r| {
+            r.bits(BitOrder::MsbFirst, |r| {
+                Ok((r.read_bit()?, r.read_bits(64)?, r.read_bits(7)?))
+            })
+        }),
+        (true, 0x03, 0x7f)
+    );
+}
+
+#[test]
+fn test_read_bits_zero() {
+    assert_eq!(
+        read_all_ok!(&[], |r| r.bits(BitOrder::MsbFirst, |r| r.read_bits(0))),
+        0
+    );
+}
+
+#[test]
+fn test_read_bits_zero_unaligned() {
+    assert_eq!(
+        read_all_ok!(&[0b1010_0000], |r| {
+            r.bits(BitOrder::MsbFirst, |r| {
+                Ok((r.read_bits(3)?, r.read_bits(0)?))
+            })
+        }),
+        (0b101, 0)
+    );
+}
+
+#[test]
+fn test_read_bits_too_wide() {
+    let err = read_all_err!(&[0; 16], |r| r
+        .bits(BitOrder::MsbFirst, |r| r.read_bits(65)));
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+#[test]
+fn test_read_bits_spanned() {
+    let bytes = [0x00, 0xff, 0x00];
+    let (value, span) = read_all_ok!(&bytes, |r| {
+        r.bits(BitOrder::MsbFirst, |r| {
+            r.skip_bits(6)?;
+            let spanned = r.read_bits_spanned(4)?;
+            r.skip_bits(14)?;
+            Ok(spanned)
+        })
+    });
+    assert_eq!(value, 0b0011);
+    assert_eq!(span.span(), Span::from(&bytes[0..2]));
+    assert_eq!(span.bit_offset(), 6);
+    assert_eq!(span.bit_len(), 4);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// BitReader::peek_bits
+
+#[test]
+fn test_peek_bits() {
+    assert_eq!(
+        read_all_ok!(&[0b1010_1010], |r| {
+            r.bits(BitOrder::MsbFirst, |r| {
+                r.skip_bits(1)?;
+                let peeked = r.peek_bits(3)?;
+                assert_eq!(r.bit_offset(), 1);
+                assert_eq!(r.read_bits(3)?, peeked);
+                Ok(peeked)
+            })
+        }),
+        0b010
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// BitReader::skip_bits / align
+
+#[test]
+fn test_skip_bits_and_align() {
+    read_all_ok!(&[0xff, 0xff], |r| {
+        r.bits(BitOrder::MsbFirst, |r| {
+            assert!(r.is_aligned());
+            r.skip_bits(3)?;
+            assert_eq!(r.remaining_bits(), 13);
+            r.align();
+            assert!(r.is_aligned());
+            assert_eq!(r.remaining_bits(), 8);
+            r.skip_bits(8)?;
+            assert!(r.at_end());
+            Ok(())
+        })
+    });
+}
+
+#[test]
+fn test_skip_bits_err() {
+    let err = read_all_err!(&[0xff], |r| r.bits(BitOrder::MsbFirst, |r| r.skip_bits(9)));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+}
@@ -2,6 +2,7 @@
 mod common;
 
 use common::*;
+use dangerous::input::{Endian, FloatPolicy, LengthPrefix};
 
 ///////////////////////////////////////////////////////////////////////////////
 // Test debug
@@ -76,6 +77,78 @@ fn test_consume_opt_u8_false() {
     }));
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::consume_ignore_ascii_case
+
+#[test]
+fn test_consume_ignore_ascii_case() {
+    read_all_ok!(b"Content-LENGTH", |r| {
+        r.consume_ignore_ascii_case(b"content-length")
+    });
+}
+
+#[test]
+fn test_consume_ignore_ascii_case_only_ascii() {
+    assert_eq!(
+        read_all_err!(b"\xc3\xa9", |r| {
+            r.consume_ignore_ascii_case(b"\xc3\x89")
+        })
+        .to_retry_requirement(),
+        None
+    );
+}
+
+#[test]
+fn test_consume_ignore_ascii_case_retry() {
+    assert_eq!(
+        read_all_err!(b"HE", |r| { r.consume_ignore_ascii_case(b"helo") }).to_retry_requirement(),
+        RetryRequirement::new(2)
+    );
+    assert_eq!(
+        read_all_err!(b"HX", |r| { r.consume_ignore_ascii_case(b"helo") }).to_retry_requirement(),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_consume_ignore_ascii_case_err_display() {
+    let err = read_all_err!(b"MAIL TO:", |r| {
+        r.consume_ignore_ascii_case(b"mail from:")
+    });
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected (ignoring ASCII case):
+            > "mail from:"
+            in:
+            > "MAIL TO:"
+               ^^^^^^^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 8
+            backtrace:
+              1. `read all input`
+              2. `consume input` (expected exact value)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::peek_eq_ignore_ascii_case
+
+#[test]
+fn test_peek_eq_ignore_ascii_case() {
+    read_all_ok!(b"EHLO", |r| {
+        assert!(r.peek_eq_ignore_ascii_case(b"ehlo"));
+        assert!(r.peek_eq_ignore_ascii_case(b"eh"));
+        assert!(!r.peek_eq_ignore_ascii_case(b"helo"));
+        assert!(!r.peek_eq_ignore_ascii_case(b"ehlo "));
+        r.skip(4)
+    });
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::take_array
 
@@ -113,6 +186,327 @@ fn test_array_opt_none() {
     );
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::read_<num>
+
+#[test]
+fn test_read_u8() {
+    assert_eq!(read_all_ok!(&[0xff], |r| r.read_u8()), 0xff);
+    assert_eq!(read_all_ok!(&[0xff], |r| r.read_i8()), -1);
+}
+
+#[test]
+fn test_read_u32_endian() {
+    let bytes = [0x01, 0x02, 0x03, 0x04];
+    assert_eq!(read_all_ok!(&bytes, |r| r.read_u32_le()), 0x0403_0201);
+    assert_eq!(read_all_ok!(&bytes, |r| r.read_u32_be()), 0x0102_0304);
+    assert_eq!(
+        read_all_ok!(&bytes, |r| r.read_u32_ne()),
+        u32::from_ne_bytes(bytes)
+    );
+}
+
+#[test]
+fn test_read_i128_be() {
+    assert_eq!(read_all_ok!(&[0xff; 16], |r| r.read_i128_be()), -1);
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_read_u16_le_err() {
+    let err = read_all_err!(&[0x01], |r| r.read_u16_le());
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to read a u16 (little endian): found 1 byte when at least 2 bytes was expected
+            > [01]
+               ^^ 
+            additional:
+              error offset: 0, input length: 1
+            backtrace:
+              1. `read all input`
+              2. `read a u16 (little endian)` (expected enough input for split)
+        "#}
+    );
+}
+
+#[test]
+fn test_read_u16_le_opt() {
+    assert_eq!(
+        read_partial_ok!(&[0x01, 0x02, 0x03], |r| Ok(r.read_u16_le_opt())),
+        (Some(0x0201), input(&[0x03]))
+    );
+    assert_eq!(
+        read_partial_ok!(&[0x01], |r| Ok(r.read_u16_le_opt())),
+        (None, input(&[0x01]))
+    );
+}
+
+#[test]
+fn test_peek_i16_be() {
+    assert_eq!(
+        read_all_ok!(&[0xff, 0xfe], |r| {
+            let v = r.peek_i16_be()?;
+            assert_eq!(r.peek_i16_be_opt(), Some(v));
+            r.skip(2)?;
+            Ok(v)
+        }),
+        -2
+    );
+}
+
+#[test]
+fn test_peek_u64_be_err() {
+    let err = read_partial_err!(&[0x01], |r| r.peek_u64_be());
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(7));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::read_<float>
+
+#[test]
+fn test_read_f32() {
+    let bytes = 1.5f32.to_le_bytes();
+    assert_eq!(
+        read_all_ok!(&bytes, |r| r.read_f32_le(FloatPolicy::normal())),
+        1.5
+    );
+    let bytes = (-2.25f64).to_be_bytes();
+    assert_eq!(
+        read_all_ok!(&bytes, |r| r.read_f64_be(FloatPolicy::normal())),
+        -2.25
+    );
+}
+
+#[test]
+fn test_read_f32_policy_any() {
+    let bytes = f32::NAN.to_le_bytes();
+    assert!(read_all_ok!(&bytes, |r| r.read_f32_le(FloatPolicy::any())).is_nan());
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_read_f32_policy_reject_nan() {
+    // Signalling NaN
+    let bytes = 0x7fa0_0000u32.to_be_bytes();
+    let err = read_all_err!(&bytes, |r| r.read_f32_be(FloatPolicy::any().reject_nan()));
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to read an f32 (big endian): expected number that is not NaN
+            > [7f a0 00 00]
+               ^^ ^^ ^^ ^^ 
+            additional:
+              error offset: 0, input length: 4
+            backtrace:
+              1. `read all input`
+              2. `read an f32 (big endian)` (expected number that is not NaN)
+        "#}
+    );
+}
+
+#[test]
+fn test_read_f64_policy_reject_infinite() {
+    let bytes = f64::NEG_INFINITY.to_le_bytes();
+    let err = read_all_err!(&bytes, |r| r.read_f64_le(FloatPolicy::finite()));
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+#[test]
+fn test_read_f64_policy_reject_subnormal() {
+    let bytes = 1u64.to_le_bytes();
+    let err = read_all_err!(&bytes, |r| r.read_f64_le(FloatPolicy::normal()));
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_eq!(
+        read_all_ok!(&bytes, |r| r.read_f64_le(FloatPolicy::finite())),
+        f64::from_bits(1)
+    );
+}
+
+#[test]
+fn test_read_f32_retry() {
+    let err = read_all_err!(&[0x00, 0x00], |r| r.read_f32_le(FloatPolicy::any()));
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::read_<leb128>
+
+#[test]
+fn test_read_uleb128() {
+    assert_eq!(read_all_ok!(&[0x00], |r| r.read_uleb128_u32()), 0);
+    assert_eq!(read_all_ok!(&[0x7f], |r| r.read_uleb128_u32()), 127);
+    assert_eq!(
+        read_all_ok!(&[0xe5, 0x8e, 0x26], |r| r.read_uleb128_u32()),
+        624_485
+    );
+    assert_eq!(
+        read_all_ok!(&[0xff, 0xff, 0xff, 0xff, 0x0f], |r| r.read_uleb128_u32()),
+        u32::MAX
+    );
+    assert_eq!(
+        read_all_ok!(
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            |r| { r.read_uleb128_u64() }
+        ),
+        u64::MAX
+    );
+}
+
+#[test]
+fn test_read_sleb128() {
+    assert_eq!(read_all_ok!(&[0x02], |r| r.read_sleb128_i32()), 2);
+    assert_eq!(read_all_ok!(&[0x7e], |r| r.read_sleb128_i32()), -2);
+    assert_eq!(read_all_ok!(&[0x80, 0x7f], |r| r.read_sleb128_i32()), -128);
+    assert_eq!(
+        read_all_ok!(&[0xc0, 0xbb, 0x78], |r| r.read_sleb128_i64()),
+        -123_456
+    );
+    assert_eq!(
+        read_all_ok!(&[0x80, 0x80, 0x80, 0x80, 0x78], |r| r.read_sleb128_i32()),
+        i32::MIN
+    );
+    assert_eq!(
+        read_all_ok!(&[0xff, 0xff, 0xff, 0xff, 0x07], |r| r.read_sleb128_i32()),
+        i32::MAX
+    );
+}
+
+#[test]
+fn test_read_zigzag() {
+    assert_eq!(read_all_ok!(&[0x00], |r| r.read_zigzag_i32()), 0);
+    assert_eq!(read_all_ok!(&[0x01], |r| r.read_zigzag_i32()), -1);
+    assert_eq!(read_all_ok!(&[0x02], |r| r.read_zigzag_i32()), 1);
+    assert_eq!(
+        read_all_ok!(&[0xff, 0xff, 0xff, 0xff, 0x0f], |r| r.read_zigzag_i32()),
+        i32::MIN
+    );
+    assert_eq!(read_all_ok!(&[0x03], |r| r.read_zigzag_i64()), -2);
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_read_uleb128_overlong() {
+    let err = read_all_err!(&[0x80, 0x00], |r| r.read_uleb128_u32());
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to read a LEB128 u32: expected canonical LEB128 encoding
+            > [80 00]
+               ^^ ^^ 
+            additional:
+              error offset: 0, input length: 2
+            backtrace:
+              1. `read all input`
+              2. `read a LEB128 u32` (expected canonical LEB128 encoding)
+        "#}
+    );
+    let err = read_all_err!(&[0xff, 0x7f], |r| r.read_sleb128_i32());
+    assert_eq!(err.to_retry_requirement(), None);
+    let err = read_all_err!(&[0x80, 0x80, 0x80, 0x80, 0x00], |r| r.read_sleb128_i32());
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+#[test]
+fn test_read_uleb128_out_of_range() {
+    let err = read_all_err!(&[0xff, 0xff, 0xff, 0xff, 0x1f], |r| r.read_uleb128_u32());
+    assert_eq!(err.to_retry_requirement(), None);
+    let err = read_all_err!(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x01], |r| {
+        r.read_uleb128_u32()
+    });
+    assert_eq!(err.to_retry_requirement(), None);
+    let err = read_all_err!(&[0x80, 0x80, 0x80, 0x80, 0x70], |r| r.read_sleb128_i32());
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+#[test]
+fn test_read_uleb128_retry() {
+    let err = read_all_err!(&[0x80, 0x80], |r| r.read_uleb128_u64());
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    let err = input!([0x80, 0x80])
+        .into_bound()
+        .read_all::<_, _, Expected<'_>>(|r| r.read_uleb128_u64())
+        .unwrap_err();
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::take_length_prefixed
+
+#[test]
+fn test_take_length_prefixed() {
+    assert_eq!(
+        read_all_ok!(&[0x02, b'h', b'i'], |r| {
+            r.take_length_prefixed(LengthPrefix::U8)
+        }),
+        b"hi"[..]
+    );
+    assert_eq!(
+        read_all_ok!(&[0x02, 0x00, 0x00, 0x00, b'h', b'i'], |r| {
+            r.take_length_prefixed(LengthPrefix::U32(Endian::Little))
+        }),
+        b"hi"[..]
+    );
+    assert_eq!(
+        read_all_ok!(&[0x02, b'h', b'i'], |r| {
+            r.take_length_prefixed(LengthPrefix::Uleb128)
+        }),
+        b"hi"[..]
+    );
+}
+
+#[test]
+fn test_take_length_prefixed_header_retry() {
+    let err = read_all_err!(&[0x00], |r| {
+        r.take_length_prefixed(LengthPrefix::U16(Endian::Big))
+    });
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+}
+
+#[test]
+fn test_take_length_prefixed_body_retry() {
+    let err = read_all_err!(&[0x00, 0x04, b'h', b'i'], |r| {
+        r.take_length_prefixed(LengthPrefix::U16(Endian::Big))
+    });
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+}
+
+#[test]
+fn test_take_length_prefixed_err_leaves_reader() {
+    read_all_ok!(&[0x00, 0x04, b'h', b'i'], |r| {
+        assert!(r
+            .recover(|r| r.take_length_prefixed(LengthPrefix::U16(Endian::Big)))
+            .is_none());
+        r.skip(4)
+    });
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_take_length_prefixed_max() {
+    let err = read_all_err!(&[0x03, b'a', b'b', b'c'], |r| {
+        r.take_length_prefixed_max(LengthPrefix::U8, 2)
+    });
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{}\n", err),
+        indoc! {r#"
+            failed to take a length prefixed input: expected length within the maximum
+            > [03 61 62 63]
+               ^^          
+            additional:
+              error offset: 0, input length: 4
+            backtrace:
+              1. `read all input`
+              2. `take a length prefixed input`
+              3. `take a length prefixed input` (expected length within the maximum)
+        "#}
+    );
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::peek_read
 
@@ -164,6 +558,18 @@ fn test_take_str_while() {
     );
 }
 
+#[test]
+fn test_take_str_while_non_ascii() {
+    assert_eq!(
+        read_all_ok!("héllo wörld".as_bytes(), |r| {
+            let v = r.take_str_while(|c| c.is_alphabetic())?;
+            r.consume(" wörld".as_bytes())?;
+            Ok(v)
+        }),
+        "héllo"[..]
+    );
+}
+
 #[test]
 fn test_take_str_while_utf8_retry() {
     // Length 1
@@ -0,0 +1,183 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use dangerous::input::Chain;
+
+macro_rules! read_chain_all {
+    ($segments:expr, $read_fn:expr) => {
+        Chain::new(&$segments).read_all::<_, _, dangerous::Expected>($read_fn)
+    };
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Chain
+
+#[test]
+fn test_chain_skips_empty_segments() {
+    let segments: [&[u8]; 4] = [b"", b"a", b"", b"b"];
+    let chain = Chain::new(&segments);
+
+    assert_eq!(chain.len(), 2);
+    assert!(!chain.is_contiguous());
+    assert!(Chain::new(&[b"" as &[u8]]).is_empty());
+    assert!(Chain::new(&[]).is_empty());
+}
+
+#[test]
+fn test_chain_read_partial() {
+    let segments: [&[u8]; 2] = [b"ab", b"cd"];
+    let (byte, rest) = Chain::new(&segments)
+        .read_partial::<_, _, Expected<'_>>(|r| r.read())
+        .unwrap();
+
+    assert_eq!(byte, b'a');
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest.to_contiguous(), None);
+}
+
+#[test]
+fn test_chain_trailing_input() {
+    let segments: [&[u8]; 2] = [b"a", b"b"];
+    let err = read_chain_all!(segments, |r| r.read()).unwrap_err();
+
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_chain_trailing_input_display() {
+    let segments: [&[u8]; 3] = [b"a", b"bc", b"d"];
+    let err = read_chain_all!(segments, |r| r.read()).unwrap_err();
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to read all input: found 3 bytes when exactly no bytes was expected
+            > "bc"
+               ^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 2
+            backtrace:
+              1. `read all input` (expected no trailing input)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// ChainReader
+
+#[test]
+fn test_read_num_straddling() {
+    let segments: [&[u8]; 3] = [b"\x01", b"\x02\x03", b"\x04\x05"];
+    assert_eq!(
+        read_chain_all!(segments, |r| Ok((r.read_u32_be()?, r.read_u8()?))).unwrap(),
+        (0x0102_0304, 0x05)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_take_borrows_within_segment() {
+    let segments: [&[u8]; 2] = [b"hello", b" world"];
+    let (hello, world) = read_chain_all!(segments, |r| Ok((r.take(5)?, r.take(6)?))).unwrap();
+
+    assert!(hello.is_borrowed());
+    assert!(Span::from(hello.as_dangerous()).is_within(Span::from(segments[0])));
+    assert!(world.is_borrowed());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_take_copies_straddling() {
+    let segments: [&[u8]; 2] = [b"hel", b"lo"];
+    let chunk = read_chain_all!(segments, |r| r.take(5)).unwrap();
+
+    assert!(!chunk.is_borrowed());
+    assert_eq!(chunk.as_dangerous(), b"hello");
+    assert_eq!(
+        chunk
+            .to_bytes()
+            .read_all::<_, _, Expected<'_>>(|r| r.take(5))
+            .unwrap(),
+        b"hello"[..]
+    );
+}
+
+#[test]
+fn test_consume_straddling() {
+    let segments: [&[u8]; 3] = [b"P", b"K", b"\x01"];
+    assert_eq!(
+        read_chain_all!(segments, |r| {
+            r.consume(b"PK")?;
+            r.read()
+        })
+        .unwrap(),
+        0x01
+    );
+}
+
+#[test]
+fn test_consume_opt_leaves_reader() {
+    let segments: [&[u8]; 2] = [b"P", b"X"];
+    assert_eq!(
+        read_chain_all!(segments, |r| {
+            assert!(!r.consume_opt(b"PK"));
+            r.take_array::<2>()
+        })
+        .unwrap(),
+        *b"PX"
+    );
+}
+
+#[test]
+fn test_err_leaves_reader() {
+    let segments: [&[u8]; 2] = [b"\x01", b"\x02"];
+    assert_eq!(
+        read_chain_all!(segments, |r| {
+            assert!(r.read_u32_le().is_err());
+            r.read_u16_be()
+        })
+        .unwrap(),
+        0x0102
+    );
+}
+
+#[test]
+fn test_retry_requirement_across_segments() {
+    let segments: [&[u8]; 2] = [b"ab", b"c"];
+    let err = read_chain_all!(segments, |r| r.skip(5)).unwrap_err();
+
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(2));
+
+    let segments: [&[u8]; 2] = [b"P", b"K"];
+    let err = read_chain_all!(segments, |r| r.consume(b"PKG")).unwrap_err();
+
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_consume_err_display() {
+    let segments: [&[u8]; 2] = [b"P", b"X\x01"];
+    let err = read_chain_all!(segments, |r| r.context("magic", |r| r.consume(b"PK"))).unwrap_err();
+
+    assert_eq!(err.to_retry_requirement(), None);
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to consume input: found a different value to the exact expected
+            expected:
+            > "K"
+            in:
+            > "X\u{1}"
+               ^      
+            additional:
+              error line: 1, error offset: 0, input length: 2
+            backtrace:
+              1. `read all input`
+              2. `<context>` (expected magic)
+              3. `consume input` (expected exact value)"#
+        }
+    );
+}
@@ -76,6 +76,29 @@ fn test_consume_opt_char_false() {
     }));
 }
 
+///////////////////////////////////////////////////////////////////////////////
+// Reader::consume_ignore_ascii_case (str)
+
+#[test]
+fn test_consume_ignore_ascii_case_str() {
+    assert_eq!(
+        read_all_ok!("SELECT inbox", |r| {
+            r.consume_ignore_ascii_case("select ")?;
+            Ok(r.take_remaining())
+        }),
+        "inbox"
+    );
+}
+
+#[test]
+fn test_peek_eq_ignore_ascii_case_str() {
+    read_all_ok!("Caf\u{e9}", |r| {
+        assert!(r.peek_eq_ignore_ascii_case("cAF\u{e9}"));
+        assert!(!r.peek_eq_ignore_ascii_case("caf\u{c9}"));
+        r.skip(4)
+    });
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 // Reader::peek_read
 
@@ -0,0 +1,213 @@
+#[macro_use]
+mod common;
+
+use common::*;
+use regex::bytes::Regex as BytesRegex;
+use regex::Regex;
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::take_regex
+
+#[test]
+fn test_take_regex_bytes() {
+    let regex = BytesRegex::new("^[0-9]+").unwrap();
+
+    assert_eq!(
+        read_partial_ok!(b"123abc", |r| r.take_regex(&regex)),
+        (input!(b"123"), input!(b"abc"))
+    );
+}
+
+#[test]
+fn test_take_regex_string() {
+    let regex = Regex::new("^\\p{L}+").unwrap();
+
+    assert_eq!(
+        read_partial_ok!("caf\u{e9} au lait", |r| r.take_regex(&regex)),
+        (input!("caf\u{e9}"), input!(" au lait"))
+    );
+}
+
+#[test]
+fn test_take_regex_anchored() {
+    let regex = BytesRegex::new("^[0-9]+").unwrap();
+    let err = read_all_err!(b"abc123", |r| r.take_regex(&regex));
+
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    assert_eq!(
+        dangerous::input(b"abc123")
+            .into_bound()
+            .read_all::<_, _, Expected<'_>>(|r| r.take_regex(&regex))
+            .unwrap_err()
+            .to_retry_requirement(),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_take_regex_err_display() {
+    let regex = BytesRegex::new("^[0-9]+").unwrap();
+    let err = read_all_err!(b"abc", |r| r.take_regex(&regex));
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to take input matching a regex: expected pattern match
+            > "abc"
+               ^^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `take input matching a regex` (expected pattern match)"#
+        }
+    );
+}
+
+#[test]
+fn test_take_regex_unanchored() {
+    let regex = BytesRegex::new("[0-9]+").unwrap();
+    let err = read_all_err!(b"123", |r| r.take_regex(&regex));
+    assert_eq!(err.to_retry_requirement(), None);
+
+    let regex = Regex::new("(?m)^[0-9]+").unwrap();
+    let err = read_all_err!("123", |r| r.take_regex(&regex));
+    assert_eq!(err.to_retry_requirement(), None);
+
+    let regex = Regex::new("\\A[0-9]+").unwrap();
+    assert_eq!(read_all_ok!("123", |r| r.take_regex(&regex)), "123");
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_take_regex_unanchored_err_display() {
+    let regex = BytesRegex::new("[0-9]+").unwrap();
+    let err = read_all_err!(b"abc", |r| r.take_regex(&regex));
+
+    assert_str_eq!(
+        format!("{:#}", err),
+        indoc! {r#"
+            failed to take input matching a regex: expected regex anchored with `^` or `\A`
+            > "abc"
+               ^^^ 
+            additional:
+              error line: 1, error offset: 0, input length: 3
+            backtrace:
+              1. `read all input`
+              2. `take input matching a regex` (expected regex anchored with `^` or `\A`)"#
+        }
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Reader::captures
+
+#[test]
+fn test_captures_bytes() {
+    let regex = BytesRegex::new("^(?P<key>[a-z]+)=(?P<value>[0-9]+)?;").unwrap();
+    let input = b"len=;max=10;";
+    let (first, second) = read_all_ok!(input, |r| Ok((r.captures(&regex)?, r.captures(&regex)?)));
+
+    assert_eq!(first.get(0).unwrap(), b"len=;"[..]);
+    assert_eq!(first.name("key").unwrap(), b"len"[..]);
+    assert_eq!(first.name("value"), None);
+    assert_eq!(first.name("missing"), None);
+    assert_eq!(second.get(1).unwrap(), b"max"[..]);
+    assert_eq!(second.iter().count(), 3);
+
+    let value = second.name("value").unwrap();
+    assert!(value.is_bound());
+    assert_eq!(value.span(), Span::from(&input[9..11]));
+    assert_eq!(
+        value
+            .read_all::<_, _, Expected<'_>>(|r| r.take_remaining_str())
+            .unwrap(),
+        "10"
+    );
+}
+
+#[test]
+fn test_captures_string() {
+    let regex = Regex::new("^([A-Z]+) (\\S+)").unwrap();
+    let input = "GET /index.html HTTP/1.1";
+    let (captures, rest) = read_partial_ok!(input, |r| r.captures(&regex));
+
+    assert_eq!(captures.get(1).unwrap(), "GET");
+    assert_eq!(captures.get(2).unwrap(), "/index.html");
+    assert_eq!(captures.get(3), None);
+    assert_eq!(
+        captures.get(2).unwrap().span(),
+        Span::from(&input.as_bytes()[4..15])
+    );
+    assert_eq!(rest, " HTTP/1.1");
+}
+
+#[test]
+fn test_captures_anchored() {
+    let regex = Regex::new("^[0-9]+").unwrap();
+    let err = read_all_err!("a1", |r| r.captures(&regex));
+
+    assert_eq!(err.to_retry_requirement(), RetryRequirement::new(1));
+    let regex = Regex::new("[0-9]+").unwrap();
+    let err = read_all_err!("1", |r| r.captures(&regex));
+
+    assert_eq!(err.to_retry_requirement(), None);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Input::split
+
+#[test]
+fn test_split_regex_empty_match() {
+    let regex = Regex::new("x*").unwrap();
+    let split: Vec<_> = input!("abc").split(&regex).collect();
+    assert_eq!(
+        split,
+        [
+            input!(""),
+            input!("a"),
+            input!("b"),
+            input!("c"),
+            input!("")
+        ]
+    );
+    let split: Vec<_> = input!("axb").split(&regex).collect();
+    assert_eq!(
+        split,
+        [input!(""), input!("a"), input!(""), input!("b"), input!("")]
+    );
+    let split: Vec<_> = input!("").split(&regex).collect();
+    assert_eq!(split, [input!(""), input!("")]);
+}
+
+#[test]
+fn test_split_regex_empty_match_multi_byte() {
+    let regex = Regex::new("").unwrap();
+    let split: Vec<_> = input!("\u{e9}a").split(&regex).collect();
+    assert_eq!(
+        split,
+        [input!(""), input!("\u{e9}"), input!("a"), input!("")]
+    );
+
+    let regex = BytesRegex::new("").unwrap();
+    let split: Vec<_> = input!(b"ab").split(&regex).collect();
+    assert_eq!(
+        split,
+        [input!(b""), input!(b"a"), input!(b"b"), input!(b"")]
+    );
+}
+
+#[test]
+fn test_splitn_regex_empty_match() {
+    let regex = Regex::new("x*").unwrap();
+    let split: Vec<_> = input!("abc").splitn(3, &regex).collect();
+    assert_eq!(split, [input!(""), input!("a"), input!("bc")]);
+}
+
+#[test]
+fn test_split_terminator_regex_empty_match() {
+    let regex = Regex::new("x*").unwrap();
+    let split: Vec<_> = input!("ab").split_terminator(&regex).collect();
+    assert_eq!(split, [input!(""), input!("a"), input!("b")]);
+}
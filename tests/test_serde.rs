@@ -0,0 +1,356 @@
+#[macro_use]
+mod common;
+
+use ::serde::de::DeserializeSeed;
+use common::*;
+
+use dangerous::error::{CoreExpected, CoreNumber, CoreOperation, Length};
+use dangerous::input::{Bound, Endian};
+
+///////////////////////////////////////////////////////////////////////////////
+// Span
+
+#[test]
+fn test_span_serialize_in() {
+    let parent = &b"hello world"[..];
+    let span = Span::from(&parent[2..5]);
+
+    assert_eq!(
+        serde_json::to_string(&span.serialize_in(parent.into())).unwrap(),
+        r#"{"start":2,"end":5}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&span.end().serialize_in(parent.into())).unwrap(),
+        r#"{"start":5,"end":5}"#
+    );
+}
+
+#[test]
+fn test_span_serialize_in_invalid() {
+    let parent = &b"hello world"[..];
+    let span = Span::from(&parent[2..5]);
+
+    assert!(serde_json::to_string(&Span::from(parent).serialize_in(span)).is_err());
+}
+
+#[test]
+fn test_span_seed() {
+    let parent = &b"hello world"[..];
+    let mut de = serde_json::Deserializer::from_str(r#"{"start":2,"end":5}"#);
+    let span = Span::seed(parent.into()).deserialize(&mut de).unwrap();
+
+    assert_eq!(span.of(parent), Some(&b"llo"[..]));
+}
+
+#[test]
+fn test_span_seed_invalid() {
+    let parent = &b"hello world"[..];
+    for offsets in [r#"{"start":2,"end":12}"#, r#"{"start":5,"end":2}"#] {
+        let mut de = serde_json::Deserializer::from_str(offsets);
+        assert!(Span::seed(parent.into()).deserialize(&mut de).is_err());
+    }
+}
+
+#[test]
+fn test_span_round_trip() {
+    let input = input!(b"hello world");
+    let error: Expected<'_> = input
+        .clone()
+        .read_all(|r| r.consume(b"hello!"))
+        .unwrap_err();
+    let span = error.backtrace().root().span;
+    let json = serde_json::to_string(&span.serialize_in(input.span())).unwrap();
+
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let deserialized = Span::seed(input.span()).deserialize(&mut de).unwrap();
+
+    assert_eq!(deserialized, span);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Value types
+
+#[test]
+fn test_bound() {
+    for bound in [Bound::None, Bound::Start, Bound::StartEnd] {
+        let json = serde_json::to_string(&bound).unwrap();
+        assert_eq!(serde_json::from_str::<Bound>(&json).unwrap(), bound);
+    }
+    assert_eq!(serde_json::to_string(&Bound::Start).unwrap(), r#""Start""#);
+}
+
+#[test]
+fn test_length() {
+    assert_eq!(
+        serde_json::to_string(&Length::AtLeast(2)).unwrap(),
+        r#"{"AtLeast":2}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Length>(r#"{"Exactly":4}"#).unwrap(),
+        Length::Exactly(4)
+    );
+}
+
+#[test]
+fn test_retry_requirement() {
+    let requirement = RetryRequirement::new(3).unwrap();
+
+    assert_eq!(serde_json::to_string(&requirement).unwrap(), "3");
+    assert_eq!(
+        serde_json::from_str::<RetryRequirement>("3").unwrap(),
+        requirement
+    );
+    assert!(serde_json::from_str::<RetryRequirement>("0").is_err());
+}
+
+#[test]
+fn test_core_operation() {
+    let operation = CoreOperation::ReadNum(CoreNumber::U16(Endian::Big));
+    let json = serde_json::to_string(&operation).unwrap();
+
+    assert_eq!(json, r#"{"ReadNum":{"U16":"Big"}}"#);
+    assert_eq!(
+        serde_json::from_str::<CoreOperation>(&json).unwrap(),
+        operation
+    );
+    assert_eq!(
+        serde_json::to_string(&CoreOperation::Consume).unwrap(),
+        r#""Consume""#
+    );
+}
+
+#[test]
+fn test_core_expected() {
+    assert_eq!(
+        serde_json::to_string(&CoreExpected::ExactValue).unwrap(),
+        r#""ExactValue""#
+    );
+    assert_eq!(
+        serde_json::to_string(&CoreExpected::EnoughInputFor("u8")).unwrap(),
+        r#"{"EnoughInputFor":"u8"}"#
+    );
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Deserializer
+
+#[cfg(feature = "alloc")]
+mod deserializer {
+    use super::*;
+
+    use ::serde::de::IgnoredAny;
+    use ::serde::Deserialize;
+    use std::string::String;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct User<'a> {
+        name: &'a str,
+        bio: String,
+        #[serde(borrow)]
+        key: &'a [u8],
+        age: u8,
+        score: Option<f64>,
+        tags: Vec<i32>,
+        role: Role,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Role {
+        Guest,
+        Member(u32),
+        Admin { level: i64 },
+    }
+
+    fn from_str<'i, T>(input: &'i str) -> Result<T, Expected<'i>>
+    where
+        T: Deserialize<'i>,
+    {
+        dangerous::serde::from_input(dangerous::input(input))
+    }
+
+    #[test]
+    fn test_struct() {
+        let input = indoc! {r#"
+            {
+                "name": "bob",
+                "bio": "likes \"quotes\"\né😀",
+                "key": "abc",
+                "age": 42,
+                "score": null,
+                "tags": [1, -2, 3],
+                "role": { "Admin": { "level": -1 } }
+            }
+        "#};
+        let user: User<'_> = from_str(input).unwrap();
+
+        assert_eq!(
+            user,
+            User {
+                name: "bob",
+                bio: "likes \"quotes\"\né😀".into(),
+                key: b"abc",
+                age: 42,
+                score: None,
+                tags: vec![1, -2, 3],
+                role: Role::Admin { level: -1 },
+            }
+        );
+        // Borrowed from the input.
+        assert!(Span::from(user.name).is_within(Span::from(input)));
+        assert!(Span::from(user.key).is_within(Span::from(input)));
+    }
+
+    #[test]
+    fn test_values() {
+        assert!(from_str::<bool>(" true ").unwrap());
+        assert_eq!(from_str::<()>("null").unwrap(), ());
+        assert_eq!(from_str::<Option<u8>>("1").unwrap(), Some(1));
+        assert_eq!(from_str::<u64>("18446744073709551615").unwrap(), u64::MAX);
+        assert_eq!(from_str::<i64>("-9223372036854775808").unwrap(), i64::MIN);
+        assert_eq!(from_str::<f64>("-0.5e1").unwrap(), -5.0);
+        assert_eq!(from_str::<f64>("1E+2").unwrap(), 100.0);
+        assert_eq!(from_str::<Role>(r#""Guest""#).unwrap(), Role::Guest);
+        assert_eq!(
+            from_str::<Role>(r#"{"Member": 7}"#).unwrap(),
+            Role::Member(7)
+        );
+        assert_eq!(
+            from_str::<(u8, String)>(r#"[1, "a\/b"]"#).unwrap(),
+            (1, "a/b".into())
+        );
+        assert_eq!(
+            from_str::<Vec<Vec<u8>>>("[[], [1]]").unwrap(),
+            vec![vec![], vec![1]]
+        );
+    }
+
+    #[test]
+    fn test_read_partial() {
+        let (value, rest) = input!(b"[1, 2] rest")
+            .read_partial::<_, _, Expected<'_>>(dangerous::serde::read::<Vec<u8>, _>)
+            .unwrap();
+
+        assert_eq!(value, [1, 2]);
+        assert_eq!(rest, b"rest"[..]);
+    }
+
+    #[test]
+    fn test_invalid_syntax() {
+        for input in [
+            "",
+            "[1,]",
+            "[1 2]",
+            r#"{"a":1,}"#,
+            r#"{a:1}"#,
+            "01",
+            "1.",
+            "-",
+            "1e",
+            "+1",
+            "nul",
+            "'a'",
+            r#""\x""#,
+            r#""\u00g0""#,
+            r#""\ud83d""#,
+            r#""\ude00""#,
+            "\"a\tb\"",
+            r#""unterminated"#,
+            "[] []",
+        ] {
+            assert!(from_str::<IgnoredAny>(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let input = input!(b"\"\xff\"");
+        let error =
+            dangerous::serde::from_input::<IgnoredAny, Expected<'_>>(input.clone()).unwrap_err();
+
+        assert_eq!(
+            error.backtrace().root().span.range_of(input.span()),
+            Some(1..2)
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let within = format!("{}{}", "[".repeat(128), "]".repeat(128));
+        let beyond = format!("{}{}", "[".repeat(129), "]".repeat(129));
+
+        assert!(from_str::<IgnoredAny>(&within).is_ok());
+        assert!(from_str::<IgnoredAny>(&beyond).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "full-backtrace")]
+    fn test_invalid_value_display() {
+        let error = from_str::<User<'_>>(r#"{"name": "bob", "age": 300}"#).unwrap_err();
+
+        assert_str_eq!(
+            format!("{:#}", error),
+            indoc! {r#"
+                failed to read and expect an external value: expected deserializable value
+                > "{\"name\": \"bob\", \"age\": 300}"
+                                                ^^^  
+                additional:
+                  error line: 1, error offset: 23, input length: 27
+                backtrace:
+                  1. `read all input`
+                  2. `<context>` (expected json value)
+                  3. `<context>` (expected User)
+                  4. `<context>` (expected age)
+                  5. `<context>` (expected json number)
+                  6. `read and expect an external value` (expected deserializable value)
+                    1. `invalid value: integer `300`, expected u8`"#
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "full-backtrace")]
+    fn test_missing_field_display() {
+        let error = from_str::<Role>(r#"{"Admin": {}}"#).unwrap_err();
+
+        assert_str_eq!(
+            format!("{:#}", error),
+            indoc! {r#"
+                failed to read and expect an external value: expected deserializable value
+                > "{\"Admin\": {}}"
+                               ^^  
+                additional:
+                  error line: 1, error offset: 10, input length: 13
+                backtrace:
+                  1. `read all input`
+                  2. `<context>` (expected json value)
+                  3. `<context>` (expected Role)
+                  4. `<context>` (expected json object)
+                  5. `read and expect an external value` (expected deserializable value)
+                    1. `missing field `level``"#
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "full-backtrace")]
+    fn test_syntax_error_display() {
+        let error = from_str::<Vec<u8>>("[1, 2,]").unwrap_err();
+
+        assert_str_eq!(
+            format!("{:#}", error),
+            indoc! {r#"
+                failed to read and expect a value: expected json value
+                > "[1, 2,]"
+                         ^ 
+                additional:
+                  error line: 1, error offset: 6, input length: 7
+                backtrace:
+                  1. `read all input`
+                  2. `<context>` (expected json value)
+                  3. `<context>` (expected json array)
+                  4. `read item at index 2`
+                  5. `read and expect a value` (expected json value)"#
+            }
+        );
+    }
+}
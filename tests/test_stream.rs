@@ -0,0 +1,185 @@
+#[macro_use]
+mod common;
+
+use std::io;
+
+use common::*;
+use dangerous::input::{Endian, LengthPrefix};
+use dangerous::stream::{StreamDecoder, StreamError};
+
+/// A stream that reads one byte at a time, recording each read.
+struct Trickle {
+    src: &'static [u8],
+    reads: usize,
+}
+
+impl Trickle {
+    fn new(src: &'static [u8]) -> Self {
+        Self { src, reads: 0 }
+    }
+}
+
+impl io::Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.src.split_first() {
+            Some((&byte, rest)) if !buf.is_empty() => {
+                buf[0] = byte;
+                self.src = rest;
+                self.reads += 1;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+/// A stream recording the largest read requested from it.
+struct Recording {
+    src: &'static [u8],
+    max_read: usize,
+}
+
+impl Recording {
+    fn new(src: &'static [u8]) -> Self {
+        Self { src, max_read: 0 }
+    }
+}
+
+impl io::Read for Recording {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.max_read = self.max_read.max(buf.len());
+        self.src.read(buf)
+    }
+}
+
+fn decode_body<R: io::Read>(
+    decoder: &mut StreamDecoder<R>,
+) -> Result<Option<Vec<u8>>, StreamError<Invalid>> {
+    decoder.decode(|r| {
+        let body = r.take_length_prefixed(LengthPrefix::U8)?;
+        Ok(body.as_dangerous().to_vec())
+    })
+}
+
+#[test]
+fn test_decode_trickle() {
+    let mut decoder = StreamDecoder::new(Trickle::new(b"\x02hi\x03foo"));
+
+    assert_eq!(decode_body(&mut decoder).unwrap(), Some(b"hi".to_vec()));
+    assert_eq!(decode_body(&mut decoder).unwrap(), Some(b"foo".to_vec()));
+    assert_eq!(decode_body(&mut decoder).unwrap(), None);
+    assert!(decoder.is_eof());
+}
+
+#[test]
+fn test_decode_reads_only_required() {
+    let stream: &[u8] = b"\x02hi\x03foo";
+    let mut decoder = StreamDecoder::new(stream);
+
+    assert_eq!(decode_body(&mut decoder).unwrap(), Some(b"hi".to_vec()));
+    assert_eq!(decoder.buffer(), b"");
+    assert_eq!(*decoder.get_ref(), b"\x03foo");
+}
+
+#[test]
+fn test_decode_compacts_consumed() {
+    let mut decoder = StreamDecoder::new(Trickle::new(b"\x01a\x01b"));
+
+    assert_eq!(
+        decoder.decode::<_, _, Invalid>(|r| r.read()).unwrap(),
+        Some(1)
+    );
+    assert_eq!(decoder.buffer(), b"");
+    assert_eq!(
+        decoder
+            .decode::<_, _, Invalid>(|r| r.take(3).map(|b| b.as_dangerous().to_vec()))
+            .unwrap(),
+        Some(b"a\x01b".to_vec())
+    );
+    assert_eq!(decoder.get_ref().reads, 4);
+}
+
+#[test]
+fn test_decode_unexpected_eof() {
+    let mut decoder = StreamDecoder::new(Trickle::new(b"\x05hi"));
+    let err = decode_body(&mut decoder).unwrap_err();
+
+    match err {
+        StreamError::Decode(err) => assert_eq!(err.to_retry_requirement(), None),
+        err => panic!("unexpected error: {}", err),
+    }
+    assert_eq!(decoder.buffer(), b"\x05hi");
+}
+
+#[test]
+fn test_decode_invalid() {
+    let mut decoder = StreamDecoder::new(Trickle::new(b"\x01\xff\x00"));
+    let err = decoder
+        .decode::<_, _, Invalid>(|r| {
+            r.take_length_prefixed(LengthPrefix::U8)?
+                .to_dangerous_str::<Invalid>()
+                .map(str::to_owned)
+        })
+        .unwrap_err();
+
+    assert!(matches!(err, StreamError::Decode(_)));
+    assert_eq!(decoder.get_ref().reads, 2);
+}
+
+#[test]
+fn test_decode_limit_exceeded() {
+    let mut decoder = StreamDecoder::new(Trickle::new(b"\xffhello")).limit(8);
+    let err = decode_body(&mut decoder).unwrap_err();
+
+    assert!(matches!(err, StreamError::LimitExceeded { required: 256 }));
+    assert_eq!(
+        err.to_string(),
+        "buffer limit exceeded: 256 bytes were required to decode"
+    );
+}
+
+#[test]
+fn test_decode_huge_length_default_limit() {
+    let stream = Recording::new(b"\xff\xff\xff\xff\xff\xff\xff\x00hi");
+    let mut decoder = StreamDecoder::new(stream);
+    let err = decoder
+        .decode::<_, _, Invalid>(|r| {
+            let body = r.take_length_prefixed(LengthPrefix::U64(Endian::Big))?;
+            Ok(body.as_dangerous().to_vec())
+        })
+        .unwrap_err();
+
+    assert!(matches!(err, StreamError::LimitExceeded { .. }));
+    assert!(decoder.get_ref().max_read <= 8);
+}
+
+#[test]
+fn test_decode_huge_length_grows_with_input() {
+    let stream = Recording::new(b"\xff\xff\xff\x00hi");
+    let mut decoder = StreamDecoder::new(stream).limit(usize::MAX);
+    let err = decoder
+        .decode::<_, _, Invalid>(|r| {
+            let body = r.take_length_prefixed(LengthPrefix::U32(Endian::Big))?;
+            Ok(body.as_dangerous().to_vec())
+        })
+        .unwrap_err();
+
+    assert!(matches!(err, StreamError::Decode(_)));
+    assert!(decoder.get_ref().max_read <= 8 * 1024);
+    assert_eq!(decoder.buffer(), b"\xff\xff\xff\x00hi");
+}
+
+#[test]
+fn test_decode_io_error() {
+    struct Broken;
+
+    impl io::Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::from(io::ErrorKind::NotConnected))
+        }
+    }
+
+    let err = decode_body(&mut StreamDecoder::new(Broken)).unwrap_err();
+
+    assert!(matches!(err, StreamError::Io(ref err) if err.kind() == io::ErrorKind::NotConnected));
+}
@@ -0,0 +1,236 @@
+#[macro_use]
+mod common;
+
+use std::cell::Cell;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use common::*;
+use dangerous::input::{Endian, LengthPrefix};
+use dangerous::stream::{AsyncStreamDecoder, StreamError};
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::{AsyncBufRead, AsyncRead};
+use futures::StreamExt;
+
+/// A stream that reads one byte at a time, returning pending before each.
+struct Trickle {
+    src: &'static [u8],
+    pending: bool,
+    reads: usize,
+}
+
+impl Trickle {
+    fn new(src: &'static [u8]) -> Self {
+        Self {
+            src,
+            pending: true,
+            reads: 0,
+        }
+    }
+}
+
+impl AsyncRead for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.pending {
+            self.pending = false;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.pending = true;
+        match self.src.split_first() {
+            Some((&byte, rest)) if !buf.is_empty() => {
+                buf[0] = byte;
+                self.src = rest;
+                self.reads += 1;
+                Poll::Ready(Ok(1))
+            }
+            _ => Poll::Ready(Ok(0)),
+        }
+    }
+}
+
+/// A buffered stream that returns its input in fixed chunks.
+struct Chunks {
+    chunks: Vec<&'static [u8]>,
+}
+
+impl Chunks {
+    fn new(chunks: &[&'static [u8]]) -> Self {
+        Self {
+            chunks: chunks.to_vec(),
+        }
+    }
+}
+
+impl AsyncRead for Chunks {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let chunk = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(chunk)) => chunk,
+            poll => return poll.map_ok(|_| 0),
+        };
+        let len = chunk.len().min(buf.len());
+        buf[..len].copy_from_slice(&chunk[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl AsyncBufRead for Chunks {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().chunks.first().copied().unwrap_or(b"")))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        if let Some(chunk) = self.chunks.first_mut() {
+            *chunk = &chunk[amt..];
+            if chunk.is_empty() {
+                self.chunks.remove(0);
+            }
+        }
+    }
+}
+
+fn body(r: &mut BytesReader<'_, Invalid>) -> Result<Vec<u8>, Invalid> {
+    let body = r.take_length_prefixed(LengthPrefix::U8)?;
+    Ok(body.as_dangerous().to_vec())
+}
+
+#[test]
+fn test_into_stream() {
+    let stream = AsyncStreamDecoder::new(Trickle::new(b"\x02hi\x03foo")).into_stream(body);
+    let bodies: Vec<_> = block_on(stream.collect());
+
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0].as_ref().unwrap(), b"hi");
+    assert_eq!(bodies[1].as_ref().unwrap(), b"foo");
+}
+
+#[test]
+fn test_into_stream_ends_after_error() {
+    let stream = AsyncStreamDecoder::new(Trickle::new(b"\x01a\x05hi\x01b")).into_stream(body);
+    let bodies: Vec<_> = block_on(stream.collect());
+
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0].as_ref().unwrap(), b"a");
+    assert!(matches!(bodies[1], Err(StreamError::Decode(_))));
+}
+
+#[test]
+fn test_poll_decode_only_retries_when_required() {
+    let calls = Cell::new(0);
+    let mut decoder = AsyncStreamDecoder::new(Trickle::new(b"\x05hello"));
+    let value = block_on(poll_fn(|cx| {
+        decoder.poll_decode(cx, |r| {
+            calls.set(calls.get() + 1);
+            body(r)
+        })
+    }))
+    .unwrap();
+
+    assert_eq!(value.unwrap(), b"hello");
+    // Empty input, the length prefix, then the full body.
+    assert_eq!(calls.get(), 3);
+    assert_eq!(decoder.get_ref().reads, 6);
+}
+
+#[test]
+fn test_poll_decode_unexpected_eof() {
+    let mut decoder = AsyncStreamDecoder::new(Trickle::new(b"\x05hi"));
+    let err = block_on(poll_fn(|cx| decoder.poll_decode(cx, body))).unwrap_err();
+
+    match err {
+        StreamError::Decode(err) => assert_eq!(err.to_retry_requirement(), None),
+        err => panic!("unexpected error: {}", err),
+    }
+    assert!(decoder.is_eof());
+    assert_eq!(decoder.buffer(), b"\x05hi");
+}
+
+#[test]
+fn test_poll_decode_limit_exceeded() {
+    let mut decoder = AsyncStreamDecoder::new(Trickle::new(b"\xffhello")).limit(8);
+    let err = block_on(poll_fn(|cx| decoder.poll_decode(cx, body))).unwrap_err();
+
+    assert!(matches!(err, StreamError::LimitExceeded { required: 256 }));
+}
+
+#[test]
+fn test_poll_decode_huge_length_default_limit() {
+    let stream: &[u8] = b"\xff\xff\xff\xff\xff\xff\xff\x00hi";
+    let mut decoder = AsyncStreamDecoder::new(stream);
+    let err = block_on(poll_fn(|cx| {
+        decoder.poll_decode::<_, _, Invalid>(cx, |r| {
+            let body = r.take_length_prefixed(LengthPrefix::U64(Endian::Big))?;
+            Ok(body.as_dangerous().to_vec())
+        })
+    }))
+    .unwrap_err();
+
+    assert!(matches!(err, StreamError::LimitExceeded { .. }));
+}
+
+#[test]
+fn test_into_buf_stream() {
+    let stream = Chunks::new(&[b"\x02hi\x05he", b"l", b"lo\x01a"]);
+    let stream = AsyncStreamDecoder::new(stream).into_buf_stream(body);
+    let bodies: Vec<_> = block_on(stream.collect());
+
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies[0].as_ref().unwrap(), b"hi");
+    assert_eq!(bodies[1].as_ref().unwrap(), b"hello");
+    assert_eq!(bodies[2].as_ref().unwrap(), b"a");
+}
+
+#[test]
+fn test_poll_decode_buf_borrows_stream_buffer() {
+    let calls = Cell::new(0);
+    let mut decoder = AsyncStreamDecoder::new(Chunks::new(&[b"\x02hi\x05he", b"llo"]));
+    let mut decode = || {
+        block_on(poll_fn(|cx| {
+            decoder.poll_decode_buf(cx, |r| {
+                calls.set(calls.get() + 1);
+                body(r)
+            })
+        }))
+    };
+
+    assert_eq!(decode().unwrap().unwrap(), b"hi");
+    assert_eq!(calls.get(), 1);
+    // The rest of the chunk is too short, so is copied and only retried once
+    // the full body has been read.
+    assert_eq!(decode().unwrap().unwrap(), b"hello");
+    assert_eq!(calls.get(), 3);
+    assert_eq!(decode().unwrap(), None);
+    assert!(decoder.is_eof());
+}
+
+#[test]
+fn test_poll_decode_buf_unexpected_eof() {
+    let mut decoder = AsyncStreamDecoder::new(Chunks::new(&[b"\x05h", b"i"]));
+    let err = block_on(poll_fn(|cx| decoder.poll_decode_buf(cx, body))).unwrap_err();
+
+    match err {
+        StreamError::Decode(err) => assert_eq!(err.to_retry_requirement(), None),
+        err => panic!("unexpected error: {}", err),
+    }
+    assert!(decoder.is_eof());
+    assert_eq!(decoder.buffer(), b"\x05hi");
+}
+
+#[test]
+fn test_poll_decode_buf_limit_exceeded() {
+    let mut decoder = AsyncStreamDecoder::new(Chunks::new(&[b"\xffhello"])).limit(8);
+    let err = block_on(poll_fn(|cx| decoder.poll_decode_buf(cx, body))).unwrap_err();
+
+    assert!(matches!(err, StreamError::LimitExceeded { required: 256 }));
+}
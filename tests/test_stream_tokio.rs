@@ -0,0 +1,134 @@
+#[macro_use]
+mod common;
+
+use std::io;
+
+use bytes::BytesMut;
+use common::*;
+use dangerous::input::{Endian, LengthPrefix};
+use dangerous::stream::Codec;
+use tokio_util::codec::Decoder;
+
+fn body<'i>(r: &mut BytesReader<'i, Expected<'i>>) -> Result<Vec<u8>, Expected<'i>> {
+    let body = r.take_length_prefixed(LengthPrefix::U8)?;
+    Ok(body.as_dangerous().to_vec())
+}
+
+#[test]
+fn test_decode_frames() {
+    let mut codec = Codec::new(body);
+    let mut src = BytesMut::from(&b"\x02hi\x03foo\x01"[..]);
+
+    assert_eq!(codec.decode(&mut src).unwrap(), Some(b"hi".to_vec()));
+    assert_eq!(codec.decode(&mut src).unwrap(), Some(b"foo".to_vec()));
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+    assert_eq!(&src[..], b"\x01");
+}
+
+#[test]
+fn test_decode_reserves_required() {
+    let mut codec = Codec::new(body);
+    let mut src = BytesMut::from(&b"\xc8"[..]);
+
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+    assert!(src.capacity() >= 201);
+}
+
+fn utf8_body<'i>(
+    r: &mut BytesReader<'i, Expected<'i>>,
+) -> Result<std::string::String, Expected<'i>> {
+    r.take_length_prefixed(LengthPrefix::U8)?
+        .to_dangerous_str()
+        .map(str::to_owned)
+}
+
+#[test]
+fn test_decode_invalid() {
+    let mut codec = Codec::new(utf8_body);
+    let err = codec
+        .decode(&mut BytesMut::from(&b"\x01\xff"[..]))
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().starts_with(
+        "failed to convert input into string: expected utf-8 code point\n> [01 ff]\n"
+    ));
+}
+
+#[test]
+#[cfg(feature = "full-backtrace")]
+fn test_decode_invalid_display() {
+    let mut codec = Codec::new(utf8_body);
+    let err = codec
+        .decode(&mut BytesMut::from(&b"\x01\xff"[..]))
+        .unwrap_err();
+
+    assert_str_eq!(
+        err.to_string(),
+        indoc! {"
+            failed to convert input into string: expected utf-8 code point
+            > [01 ff]
+                  ^^ 
+            additional:
+              error offset: 1, input length: 2
+            backtrace:
+              1. `read a partial length of input`
+              2. `convert input into string` (expected utf-8 code point)"
+        }
+    );
+}
+
+#[test]
+fn test_decode_eof_incomplete() {
+    let mut codec = Codec::new(body);
+    let mut src = BytesMut::from(&b"\x05hi"[..]);
+
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+    assert_eq!(
+        codec.decode_eof(&mut src).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(codec.decode_eof(&mut BytesMut::new()).unwrap(), None);
+}
+
+#[test]
+fn test_decode_limit_exceeded() {
+    let mut codec = Codec::new(body).limit(8);
+    let err = codec
+        .decode(&mut BytesMut::from(&b"\xffhello"[..]))
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "buffer limit exceeded: 256 bytes were required to decode"
+    );
+}
+
+#[test]
+fn test_decode_huge_length_default_limit() {
+    let mut codec = Codec::new(|r| {
+        let body = r.take_length_prefixed(LengthPrefix::U64(Endian::Big))?;
+        Ok(body.as_dangerous().to_vec())
+    });
+    let err = codec
+        .decode(&mut BytesMut::from(
+            &b"\xff\xff\xff\xff\xff\xff\xff\x00hi"[..],
+        ))
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().starts_with("buffer limit exceeded"));
+}
+
+#[test]
+fn test_decode_huge_length_bounded_reserve() {
+    let mut codec = Codec::new(|r| {
+        let body = r.take_length_prefixed(LengthPrefix::U32(Endian::Big))?;
+        Ok(body.as_dangerous().to_vec())
+    })
+    .limit(usize::MAX);
+    let mut src = BytesMut::from(&b"\xff\xff\xff\x00hi"[..]);
+
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+    assert!(src.capacity() <= 64 * 1024);
+}